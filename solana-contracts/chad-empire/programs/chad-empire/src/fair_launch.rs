@@ -1,12 +1,24 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
-use solana_program::{program::invoke, system_instruction};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use solana_program::{
+    clock::DEFAULT_MS_PER_SLOT,
+    keccak,
+    program::{invoke, invoke_signed},
+    slot_hashes, system_instruction,
+};
 use std::convert::TryInto;
 
+use crate::liquidity_pool::{self, AmmPool};
+use crate::LpLock;
+use crate::{Proposal, RewardsPoolConfig, TokenConfig};
+use crate::staking::{self, StakeAccount, StakingConfig};
+use crate::vesting::calculate_claimable_amount;
+
 #[account]
 pub struct FairLaunchConfig {
     pub authority: Pubkey,
     pub token_mint: Pubkey,
+    pub round_id: u16,              // Launch round of the mint, e.g. private, public, community
     pub sol_vault: Pubkey,
     pub token_vault: Pubkey,
     pub start_time: i64,
@@ -16,23 +28,80 @@ pub struct FairLaunchConfig {
     pub liquidity_created: bool,
     pub investment_days: u8,
     pub referral_bonus_bps: u16, // Basis points for referral bonus (e.g., 1000 = 10%)
+    pub days_initialized: u8,       // Number of days whose DailyStats exist
+    pub days_supply_generated: u8,  // Number of days whose supply has been generated
+    pub whitelist_root: [u8; 32],   // Merkle root of (wallet, cap) leaves, zero if there is no whitelist
+    pub whitelist_end_time: i64,    // Only whitelisted wallets can contribute before this time
+    pub soft_cap: u64,              // SOL that must be raised by end_time, otherwise contributions are refunded
+    pub hard_cap: u64,              // Maximum SOL raised, 0 for no limit
+    pub max_per_wallet: u64,        // Maximum SOL contributed by one wallet, 0 for no limit
+    pub cancelled: bool,            // Set by the authority to cancel the launch and open refunds
+    pub total_sol_refunded: u64,
+    pub claim_tge_release_bps: u16, // Share of each allocation claimable once liquidity is created
+    pub claim_vesting_days: u16,    // The rest unlocks linearly over this many days
+    pub claim_start_time: i64,      // Set when liquidity is created
+    pub stake_bonus_bps: u16,       // Loyalty bonus on tokens staked through claim_and_stake
+    pub total_stake_bonus_paid: u64,
+    pub marketing_wallet: Pubkey,   // Recipients of the SOL left in sol_vault after liquidity creation
+    pub team_wallet: Pubkey,
+    pub treasury_wallet: Pubkey,
+    pub marketing_share_bps: u16,
+    pub team_share_bps: u16,
+    pub treasury_share_bps: u16,
+    pub total_marketing_withdrawn: u64,
+    pub total_team_withdrawn: u64,
+    pub total_treasury_withdrawn: u64,
+    pub total_native_sol_raised: u64, // Part of total_sol_raised contributed in native SOL
+    pub payment_mint_count: u8,       // Number of PaymentMint accounts added
+    pub lp_lock_seconds: i64,         // LP lock duration used by create_liquidity
+    pub lp_release_governance: Pubkey, // If set, releasing the LP needs an executed proposal of this governance
+    pub keeper_bounty_lamports: u64,  // Paid to the caller of each launch crank, from keeper_bounty_budget
+    pub keeper_bounty_tokens: u64,    // Paid to the caller of each launch crank, from keeper_bounty_token_budget
+    pub keeper_bounty_budget: u64,    // SOL funded into sol_vault by the authority for keeper bounties
+    pub total_keeper_bounty_lamports: u64,
+    pub total_keeper_bounty_tokens: u64,
+    pub claim_deadline: i64,          // Unclaimed tokens can be swept after this time, 0 for no deadline
+    pub sweep_governance: Pubkey,     // If set, an executed proposal of this governance can also authorize sweeps
+    pub total_tokens_forfeited: u64,  // Unclaimed allocations and referral bonuses swept after the deadline
+    pub total_tokens_burned: u64,     // Part of total_tokens_forfeited that was burned
+    pub stake_bonus_budget: u64,      // Tokens funded into token_vault by the authority for stake bonuses
+    pub keeper_bounty_token_budget: u64, // Tokens funded into token_vault by the authority for keeper bounties
 }
 
 impl FairLaunchConfig {
-    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 2;
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 1 + 1 + 2 + 1 + 1 + 32 + 8 + 8 + 8 + 8 + 1 + 8 + 2 + 2 + 8 + 2 + 8
+        + 32 + 32 + 32 + 2 + 2 + 2 + 8 + 8 + 8 + 8 + 1 + 8 + 32 + 8 + 8 + 8 + 8 + 8 + 2
+        + 8 + 32 + 8 + 8 + 8 + 8;
+    pub const MAX_INVESTMENT_DAYS: u8 = 7;
+    pub const MAX_PAYMENT_MINTS: u8 = 4;
+    pub const MAX_KEEPER_BOUNTY_LAMPORTS: u64 = 50_000_000; // 0.05 SOL
+    
+    // Launch cranks reported in LaunchCrankExecuted
+    pub const CRANK_GENERATE_DAILY_SUPPLY: u8 = 1;
+    pub const CRANK_CREATE_LIQUIDITY: u8 = 2;
+    pub const MAX_STAKE_BONUS_BPS: u16 = 2000; // 20%
+    pub const SUPPLY_REVEAL_WINDOW_SECONDS: i64 = 86400; // After the day ends, then the supply falls back to max_supply
+    pub const MIN_CLAIM_WINDOW_SECONDS: i64 = 90 * 86400; // After the claim schedule fully unlocks
 }
 
 #[account]
 pub struct InvestorInfo {
-    pub investor: Pubkey,
+    pub investor: Pubkey, // Current holder of the position, entitled to its claims and refunds
     pub total_sol_invested: u64,
     pub total_tokens_allocated: u64,
-    pub claimed: bool,
+    pub claimed: bool,    // Set once total_tokens_allocated has been computed on the first claim
     pub referrer: Pubkey, // Address of the referrer, if any
+    pub sol_invested_per_day: [u64; 7], // SOL-equivalent contributed on each investment day
+    pub tokens_claimed: u64,
+    pub native_sol_invested: u64,
+    pub payment_mint_invested: [u64; 4], // Tokens contributed in each payment mint, by PaymentMint index
+    pub tokens_forfeited: u64, // Unclaimed allocation swept after the claim deadline
+    pub forfeited_at: i64,     // When the allocation was swept, 0 if it wasn't
+    pub contributor: Pubkey,   // Wallet that contributed, the PDA seed; differs from investor once transferred
 }
 
 impl InvestorInfo {
-    pub const LEN: usize = 32 + 8 + 8 + 1 + 32;
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 32 + (8 * 7) + 8 + 8 + (8 * 4) + 8 + 8 + 32;
 }
 
 #[account]
@@ -44,10 +113,83 @@ pub struct DailyStats {
     pub max_supply: u64,
     pub actual_supply: u64,
     pub supply_generated: bool,
+    pub native_sol_invested: u64,
+    pub payment_mint_invested: [u64; 4], // Tokens contributed in each payment mint, by PaymentMint index
+    pub supply_commitment: [u8; 32],     // keccak(seed), committed by the authority before the day opens
+    pub supply_slot: u64,                // Estimated first slot after the day ends, fixed at commit time
+    pub revealed_seed: [u8; 32],         // Seed revealed after the day closes
+    pub slot_hash_slot: u64,             // First slot at or after supply_slot, whose hash is mixed into the randomness
+    pub slot_hash: [u8; 32],
 }
 
 impl DailyStats {
-    pub const LEN: usize = 1 + 8 + 8 + 8 + 8 + 8 + 1;
+    pub const LEN: usize = 1 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + (8 * 4) + 32 + 8 + 32 + 8 + 32;
+}
+
+#[event]
+pub struct LaunchCrankExecuted {
+    pub fair_launch_config: Pubkey,
+    pub keeper: Pubkey,
+    pub crank: u8, // FairLaunchConfig::CRANK_*
+    pub day: u8,   // 0 for create_liquidity
+    pub bounty_lamports: u64,
+    pub bounty_tokens: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DailySupplyGenerated {
+    pub fair_launch_config: Pubkey,
+    pub day: u8,
+    pub revealed_seed: [u8; 32],
+    pub slot_hash_slot: u64,
+    pub slot_hash: [u8; 32],
+    pub randomness: [u8; 32],
+    pub actual_supply: u64,
+    pub fallback: bool, // The seed wasn't revealed before the deadline
+}
+
+#[event]
+pub struct UnclaimedTokensSwept {
+    pub fair_launch_config: Pubkey,
+    pub owner: Pubkey,        // Investor or referrer whose tokens were forfeited
+    pub referral_bonus: bool, // Whether the tokens were a referral bonus rather than an allocation
+    pub amount: u64,
+    pub burned: bool,         // Burned, or moved to the rewards vault
+    pub swept_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InvestorPositionTransferred {
+    pub fair_launch_config: Pubkey,
+    pub investor_info: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub timestamp: i64,
+}
+
+#[account]
+pub struct PaymentMint {
+    pub fair_launch_config: Pubkey,
+    pub mint: Pubkey,
+    pub vault: Pubkey,              // Token account of the fair launch config holding contributions
+    pub index: u8,                  // Slot in the per-mint contribution arrays
+    pub lamports_per_token: u64,    // SOL-equivalent value of one whole token, in lamports
+    pub rate_fixed: bool,           // If set, the rate can't be changed after the mint is added
+    pub enabled: bool,
+    pub total_contributed: u64,
+    pub total_refunded: u64,
+    pub total_withdrawn: u64,
+}
+
+impl PaymentMint {
+    pub const LEN: usize = 32 + 32 + 32 + 1 + 8 + 1 + 1 + 8 + 8 + 8;
+    
+    // SOL-equivalent value of an amount of the mint, in lamports
+    pub fn sol_equivalent(&self, amount: u64, decimals: u8) -> u64 {
+        ((amount as u128) * (self.lamports_per_token as u128) / 10u128.pow(decimals as u32)) as u64
+    }
 }
 
 #[account]
@@ -56,24 +198,36 @@ pub struct ReferralInfo {
     pub total_sol_referred: u64,
     pub total_bonus_tokens: u64,
     pub claimed: bool,
+    pub bonus_forfeited: u64, // Unclaimed bonus swept after the claim deadline
+    pub forfeited_at: i64,    // When the bonus was swept, 0 if it wasn't
 }
 
 impl ReferralInfo {
-    pub const LEN: usize = 32 + 8 + 8 + 1;
+    pub const LEN: usize = 32 + 8 + 8 + 1 + 8 + 8;
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn initialize_fair_launch(
     ctx: Context<InitializeFairLaunch>,
+    round_id: u16,
     start_time: i64,
     investment_days: u8,
     referral_bonus_bps: u16,
+    soft_cap: u64,
+    hard_cap: u64,
+    max_per_wallet: u64,
 ) -> Result<()> {
-    require!(investment_days > 0 && investment_days <= 7, ErrorCode::InvalidInvestmentDays);
+    require!(hard_cap == 0 || soft_cap <= hard_cap, ErrorCode::InvalidCaps);
+    require!(
+        investment_days > 0 && investment_days <= FairLaunchConfig::MAX_INVESTMENT_DAYS,
+        ErrorCode::InvalidInvestmentDays
+    );
     require!(referral_bonus_bps <= 1000, ErrorCode::ReferralBonusTooHigh); // Max 10% referral bonus
 
     let fair_launch_config = &mut ctx.accounts.fair_launch_config;
     fair_launch_config.authority = ctx.accounts.authority.key();
     fair_launch_config.token_mint = ctx.accounts.token_mint.key();
+    fair_launch_config.round_id = round_id;
     fair_launch_config.sol_vault = ctx.accounts.sol_vault.key();
     fair_launch_config.token_vault = ctx.accounts.token_vault.key();
     fair_launch_config.start_time = start_time;
@@ -83,27 +237,112 @@ pub fn initialize_fair_launch(
     fair_launch_config.liquidity_created = false;
     fair_launch_config.investment_days = investment_days;
     fair_launch_config.referral_bonus_bps = referral_bonus_bps;
+    fair_launch_config.days_initialized = 0;
+    fair_launch_config.days_supply_generated = 0;
+    fair_launch_config.whitelist_root = [0; 32];
+    fair_launch_config.whitelist_end_time = 0;
+    fair_launch_config.soft_cap = soft_cap;
+    fair_launch_config.hard_cap = hard_cap;
+    fair_launch_config.max_per_wallet = max_per_wallet;
+    fair_launch_config.cancelled = false;
+    fair_launch_config.total_sol_refunded = 0;
+    fair_launch_config.claim_tge_release_bps = 10000; // Everything claimable at once until a schedule is set
+    fair_launch_config.claim_vesting_days = 0;
+    fair_launch_config.claim_start_time = 0;
+    fair_launch_config.stake_bonus_bps = 0;
+    fair_launch_config.total_stake_bonus_paid = 0;
+    fair_launch_config.marketing_wallet = Pubkey::default();
+    fair_launch_config.team_wallet = Pubkey::default();
+    fair_launch_config.treasury_wallet = Pubkey::default();
+    fair_launch_config.marketing_share_bps = 0;
+    fair_launch_config.team_share_bps = 0;
+    fair_launch_config.treasury_share_bps = 0;
+    fair_launch_config.total_marketing_withdrawn = 0;
+    fair_launch_config.total_team_withdrawn = 0;
+    fair_launch_config.total_treasury_withdrawn = 0;
+    fair_launch_config.total_native_sol_raised = 0;
+    fair_launch_config.payment_mint_count = 0;
+    fair_launch_config.lp_lock_seconds = LpLock::MIN_LOCK_SECONDS;
+    fair_launch_config.lp_release_governance = Pubkey::default();
+    fair_launch_config.keeper_bounty_lamports = 0;
+    fair_launch_config.keeper_bounty_tokens = 0;
+    fair_launch_config.keeper_bounty_budget = 0;
+    fair_launch_config.total_keeper_bounty_lamports = 0;
+    fair_launch_config.total_keeper_bounty_tokens = 0;
+    fair_launch_config.claim_deadline = 0;
+    fair_launch_config.sweep_governance = Pubkey::default();
+    fair_launch_config.total_tokens_forfeited = 0;
+    fair_launch_config.total_tokens_burned = 0;
+    fair_launch_config.stake_bonus_budget = 0;
+    fair_launch_config.keeper_bounty_token_budget = 0;
 
-    // Initialize daily stats for each investment day
-    for day in 1..=investment_days {
-        let seeds = &[
-            b"daily_stats".as_ref(),
-            &[day],
-            ctx.accounts.fair_launch_config.key().as_ref(),
-            &[ctx.bumps.daily_stats],
-        ];
-        let signer = &[&seeds[..]];
+    // The DailyStats of each day are created with init_daily_stats
 
-        let daily_stats = &mut ctx.accounts.daily_stats;
-        daily_stats.day = day;
-        daily_stats.total_sol_invested = 0;
-        daily_stats.total_tokens_allocated = 0;
-        daily_stats.min_supply = 5_000_000; // 5 million tokens minimum per day
-        daily_stats.max_supply = 10_000_000; // 10 million tokens maximum per day
-        daily_stats.actual_supply = 0;
-        daily_stats.supply_generated = false;
-    }
+    Ok(())
+}
+
+// Create the DailyStats of a day with its supply range, in base units of the token
+pub fn init_daily_stats(
+    ctx: Context<InitDailyStats>,
+    day: u8,
+    min_supply: u64,
+    max_supply: u64,
+) -> Result<()> {
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    
+    // Check if the day is valid
+    require!(
+        day > 0 && day <= fair_launch_config.investment_days,
+        ErrorCode::InvalidInvestmentDay
+    );
+    
+    // The random supply is picked from [min_supply, max_supply)
+    require!(min_supply < max_supply, ErrorCode::InvalidSupplyRange);
+    
+    let daily_stats = &mut ctx.accounts.daily_stats;
+    daily_stats.day = day;
+    daily_stats.total_sol_invested = 0;
+    daily_stats.total_tokens_allocated = 0;
+    daily_stats.min_supply = min_supply;
+    daily_stats.max_supply = max_supply;
+    daily_stats.actual_supply = 0;
+    daily_stats.supply_generated = false;
+    daily_stats.native_sol_invested = 0;
+    daily_stats.payment_mint_invested = [0; 4];
+    daily_stats.supply_commitment = [0; 32];
+    daily_stats.supply_slot = 0;
+    daily_stats.revealed_seed = [0; 32];
+    daily_stats.slot_hash_slot = 0;
+    daily_stats.slot_hash = [0; 32];
+    
+    fair_launch_config.days_initialized = fair_launch_config.days_initialized.checked_add(1).unwrap();
+    
+    Ok(())
+}
 
+// Set or rotate the whitelist, only before the fair launch starts
+// A zero root disables the whitelist window
+pub fn set_whitelist(
+    ctx: Context<SetWhitelist>,
+    whitelist_root: [u8; 32],
+    whitelist_end_time: i64,
+) -> Result<()> {
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(current_time < fair_launch_config.start_time, ErrorCode::FairLaunchAlreadyStarted);
+    
+    if whitelist_root != [0; 32] {
+        require!(
+            whitelist_end_time > fair_launch_config.start_time &&
+            whitelist_end_time <= fair_launch_config.end_time,
+            ErrorCode::InvalidWhitelistWindow
+        );
+    }
+    
+    fair_launch_config.whitelist_root = whitelist_root;
+    fair_launch_config.whitelist_end_time = whitelist_end_time;
+    
     Ok(())
 }
 
@@ -112,11 +351,142 @@ pub fn contribute(
     amount: u64,
     day: u8,
     referrer: Option<Pubkey>,
+    whitelist_cap: u64,
+    whitelist_proof: Vec<[u8; 32]>,
 ) -> Result<()> {
-    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    let investor_key = ctx.accounts.investor.key();
+    check_contribution(
+        &ctx.accounts.fair_launch_config,
+        &ctx.accounts.daily_stats,
+        &ctx.accounts.investor_info,
+        &investor_key,
+        amount,
+        day,
+        whitelist_cap,
+        &whitelist_proof,
+    )?;
+    
+    // Transfer SOL to the vault
+    invoke(
+        &system_instruction::transfer(
+            ctx.accounts.investor.key,
+            ctx.accounts.sol_vault.key,
+            amount,
+        ),
+        &[
+            ctx.accounts.investor.to_account_info(),
+            ctx.accounts.sol_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+    
+    record_contribution(
+        &mut ctx.accounts.fair_launch_config,
+        &mut ctx.accounts.daily_stats,
+        &mut ctx.accounts.investor_info,
+        ctx.accounts.referral_info.as_deref_mut(),
+        investor_key,
+        amount,
+        day,
+        referrer,
+    )?;
+    
+    // Native SOL is tracked separately, as only it can seed the pool and be refunded from sol_vault
+    let investor_info = &mut ctx.accounts.investor_info;
+    investor_info.native_sol_invested = investor_info.native_sol_invested.checked_add(amount).unwrap();
     let daily_stats = &mut ctx.accounts.daily_stats;
+    daily_stats.native_sol_invested = daily_stats.native_sol_invested.checked_add(amount).unwrap();
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.total_native_sol_raised = fair_launch_config.total_native_sol_raised.checked_add(amount).unwrap();
+    
+    Ok(())
+}
+
+// Contribute in a payment mint, counted at its SOL-equivalent value for allocation and caps
+pub fn contribute_token(
+    ctx: Context<ContributeToken>,
+    amount: u64,
+    day: u8,
+    referrer: Option<Pubkey>,
+    whitelist_cap: u64,
+    whitelist_proof: Vec<[u8; 32]>,
+) -> Result<()> {
+    let payment_mint = &ctx.accounts.payment_mint;
+    require!(payment_mint.enabled, ErrorCode::PaymentMintDisabled);
+    
+    // Only the amount that reaches the vault after any transfer fee is credited
+    let transfer_fee = crate::transfer_fee::calculate_transfer_fee(
+        &ctx.accounts.mint.to_account_info(),
+        amount,
+    )?;
+    let received_amount = amount.checked_sub(transfer_fee).unwrap();
+    let sol_equivalent = payment_mint.sol_equivalent(received_amount, ctx.accounts.mint.decimals);
+    
+    let investor_key = ctx.accounts.investor.key();
+    check_contribution(
+        &ctx.accounts.fair_launch_config,
+        &ctx.accounts.daily_stats,
+        &ctx.accounts.investor_info,
+        &investor_key,
+        sol_equivalent,
+        day,
+        whitelist_cap,
+        &whitelist_proof,
+    )?;
+    
+    // Transfer the payment tokens to the mint's vault
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.investor_token_account.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.payment_vault.to_account_info(),
+                authority: ctx.accounts.investor.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    record_contribution(
+        &mut ctx.accounts.fair_launch_config,
+        &mut ctx.accounts.daily_stats,
+        &mut ctx.accounts.investor_info,
+        ctx.accounts.referral_info.as_deref_mut(),
+        investor_key,
+        sol_equivalent,
+        day,
+        referrer,
+    )?;
+    
+    let index = ctx.accounts.payment_mint.index as usize;
     let investor_info = &mut ctx.accounts.investor_info;
+    investor_info.payment_mint_invested[index] = investor_info.payment_mint_invested[index]
+        .checked_add(received_amount)
+        .unwrap();
+    let daily_stats = &mut ctx.accounts.daily_stats;
+    daily_stats.payment_mint_invested[index] = daily_stats.payment_mint_invested[index]
+        .checked_add(received_amount)
+        .unwrap();
+    let payment_mint = &mut ctx.accounts.payment_mint;
+    payment_mint.total_contributed = payment_mint.total_contributed.checked_add(received_amount).unwrap();
     
+    Ok(())
+}
+
+// Helper function to check that a contribution of a SOL-equivalent amount is allowed
+#[allow(clippy::too_many_arguments)]
+fn check_contribution(
+    fair_launch_config: &FairLaunchConfig,
+    daily_stats: &DailyStats,
+    investor_info: &InvestorInfo,
+    investor: &Pubkey,
+    amount: u64,
+    day: u8,
+    whitelist_cap: u64,
+    whitelist_proof: &[[u8; 32]],
+) -> Result<()> {
     // Check if fair launch is active
     let current_time = Clock::get()?.unix_timestamp;
     require!(
@@ -124,6 +494,13 @@ pub fn contribute(
         current_time <= fair_launch_config.end_time,
         ErrorCode::FairLaunchNotActive
     );
+    require!(!fair_launch_config.cancelled, ErrorCode::FairLaunchCancelled);
+    
+    // A position transferred to another wallet can't be added to by its contributor
+    require!(
+        investor_info.investor == Pubkey::default() || investor_info.investor == *investor,
+        ErrorCode::PositionTransferred
+    );
     
     // Check if the selected day is valid
     require!(
@@ -135,37 +512,77 @@ pub fn contribute(
     let day_start = fair_launch_config.start_time + ((day - 1) as i64 * 86400);
     require!(current_time <= day_start + 86400, ErrorCode::DayAlreadyPassed);
     
+    // The day's supply must be committed before anyone contributes to it
+    require!(daily_stats.supply_commitment != [0; 32], ErrorCode::SupplyNotCommitted);
+    
     // Check minimum contribution
     require!(amount >= 50_000_000, ErrorCode::ContributionTooSmall); // 0.05 SOL minimum
     
-    // Transfer SOL to the vault
-    invoke(
-        &system_instruction::transfer(
-            ctx.accounts.investor.key,
-            ctx.accounts.sol_vault.key,
-            amount,
-        ),
-        &[
-            ctx.accounts.investor.to_account_info(),
-            ctx.accounts.sol_vault.to_account_info(),
-            ctx.accounts.system_program.to_account_info(),
-        ],
-    )?;
+    // Check the hard cap and the per-wallet cap
+    if fair_launch_config.hard_cap > 0 {
+        require!(
+            fair_launch_config.total_sol_raised.checked_add(amount).unwrap() <= fair_launch_config.hard_cap,
+            ErrorCode::HardCapExceeded
+        );
+    }
+    if fair_launch_config.max_per_wallet > 0 {
+        require!(
+            investor_info.total_sol_invested.checked_add(amount).unwrap() <= fair_launch_config.max_per_wallet,
+            ErrorCode::WalletCapExceeded
+        );
+    }
+    
+    // During the whitelist window only listed wallets can contribute, up to their cap
+    if fair_launch_config.whitelist_root != [0; 32] && current_time < fair_launch_config.whitelist_end_time {
+        require!(
+            verify_whitelist_proof(
+                &fair_launch_config.whitelist_root,
+                investor,
+                whitelist_cap,
+                whitelist_proof,
+            ),
+            ErrorCode::NotWhitelisted
+        );
+        require!(
+            investor_info.total_sol_invested.checked_add(amount).unwrap() <= whitelist_cap,
+            ErrorCode::WhitelistCapExceeded
+        );
+    }
     
+    Ok(())
+}
+
+// Helper function to record a contribution of a SOL-equivalent amount
+#[allow(clippy::too_many_arguments)]
+fn record_contribution(
+    fair_launch_config: &mut FairLaunchConfig,
+    daily_stats: &mut DailyStats,
+    investor_info: &mut InvestorInfo,
+    referral_info: Option<&mut ReferralInfo>,
+    investor: Pubkey,
+    amount: u64,
+    day: u8,
+    referrer: Option<Pubkey>,
+) -> Result<()> {
     // Update investor info
     if investor_info.investor == Pubkey::default() {
-        investor_info.investor = ctx.accounts.investor.key();
+        investor_info.investor = investor;
+        investor_info.contributor = investor;
     }
     investor_info.total_sol_invested = investor_info.total_sol_invested.checked_add(amount).unwrap();
+    let day_index = (day - 1) as usize;
+    investor_info.sol_invested_per_day[day_index] = investor_info.sol_invested_per_day[day_index]
+        .checked_add(amount)
+        .unwrap();
     investor_info.claimed = false;
     
     // Set referrer if provided and valid
     if let Some(ref_pubkey) = referrer {
-        if ref_pubkey != ctx.accounts.investor.key() && ref_pubkey != Pubkey::default() {
+        if ref_pubkey != investor && ref_pubkey != Pubkey::default() {
             investor_info.referrer = ref_pubkey;
             
             // Update referral info if it exists
-            if let Some(referral_info) = &mut ctx.accounts.referral_info {
+            if let Some(referral_info) = referral_info {
                 if referral_info.referrer == Pubkey::default() {
                     referral_info.referrer = ref_pubkey;
                 }
@@ -186,7 +603,87 @@ pub fn contribute(
     Ok(())
 }
 
-pub fn generate_daily_supply(ctx: Context<GenerateDailySupply>, day: u8) -> Result<()> {
+// Helper function to verify a whitelist Merkle proof
+// Leaves are keccak(wallet || cap as u64 little endian), pairs are hashed in sorted order
+pub fn verify_whitelist_proof(
+    root: &[u8; 32],
+    investor: &Pubkey,
+    cap: u64,
+    proof: &[[u8; 32]],
+) -> bool {
+    let mut computed = keccak::hashv(&[investor.as_ref(), &cap.to_le_bytes()]).0;
+    for node in proof {
+        computed = if computed <= *node {
+            keccak::hashv(&[&computed, node]).0
+        } else {
+            keccak::hashv(&[node, &computed]).0
+        };
+    }
+    computed == *root
+}
+
+// Commit to the seed of a day's supply, before the day opens
+pub fn commit_daily_supply(ctx: Context<CommitDailySupply>, day: u8, commitment: [u8; 32]) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    
+    // Check if the day is valid
+    require!(
+        day > 0 && day <= fair_launch_config.investment_days,
+        ErrorCode::InvalidInvestmentDay
+    );
+    
+    // The commitment can be replaced until the day opens
+    let clock = Clock::get()?;
+    let current_time = clock.unix_timestamp;
+    let day_start = fair_launch_config.start_time + ((day - 1) as i64 * 86400);
+    require!(current_time < day_start, ErrorCode::DayAlreadyOpened);
+    require!(commitment != [0; 32], ErrorCode::InvalidCommitment);
+    
+    // Fix the slot whose hash is mixed into the randomness now, so the time of the reveal can't pick it
+    let day_end = day_start + 86400;
+    let slots_until_day_end = ((day_end - current_time) as u64)
+        .checked_mul(1000)
+        .unwrap()
+        .checked_div(DEFAULT_MS_PER_SLOT)
+        .unwrap();
+    
+    let daily_stats = &mut ctx.accounts.daily_stats;
+    daily_stats.supply_commitment = commitment;
+    daily_stats.supply_slot = clock.slot.checked_add(slots_until_day_end).unwrap().checked_add(1).unwrap();
+    
+    Ok(())
+}
+
+// Record the hash of a day's supply slot while it is still in the SlotHashes sysvar
+// Callable by anyone, so the reveal doesn't have to land within the sysvar's history
+pub fn record_supply_slot_hash(ctx: Context<RecordSupplySlotHash>, day: u8) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    let daily_stats = &mut ctx.accounts.daily_stats;
+    
+    // Check if the day is valid
+    require!(
+        day > 0 && day <= fair_launch_config.investment_days,
+        ErrorCode::InvalidInvestmentDay
+    );
+    require!(daily_stats.supply_slot > 0, ErrorCode::SupplyNotCommitted);
+    
+    // Already recorded or generated, nothing to do
+    if daily_stats.slot_hash != [0; 32] || daily_stats.supply_generated {
+        return Ok(());
+    }
+    
+    require!(
+        record_slot_hash(daily_stats, &ctx.accounts.slot_hashes)?,
+        ErrorCode::SupplySlotHashUnavailable
+    );
+    
+    Ok(())
+}
+
+// Reveal the committed seed once the day has closed and derive the day's supply
+// Callable by anyone who has the seed; once the supply is generated, further calls do nothing.
+// If the seed isn't revealed within SUPPLY_REVEAL_WINDOW_SECONDS, anyone can generate the day at max_supply
+pub fn generate_daily_supply(ctx: Context<GenerateDailySupply>, day: u8, seed: [u8; 32]) -> Result<()> {
     let fair_launch_config = &ctx.accounts.fair_launch_config;
     let daily_stats = &mut ctx.accounts.daily_stats;
     
@@ -201,362 +698,2664 @@ pub fn generate_daily_supply(ctx: Context<GenerateDailySupply>, day: u8) -> Resu
     let day_end = fair_launch_config.start_time + (day as i64 * 86400);
     require!(current_time > day_end, ErrorCode::DayNotYetEnded);
     
-    // Check if supply has already been generated
-    require!(!daily_stats.supply_generated, ErrorCode::SupplyAlreadyGenerated);
+    // Already generated, nothing to do
+    if daily_stats.supply_generated {
+        return Ok(());
+    }
     
-    // Check if there were any investments for this day
-    require!(daily_stats.total_sol_invested > 0, ErrorCode::NoInvestmentsForDay);
+    // A day without investments generates no supply, but still counts as generated
+    if daily_stats.total_sol_invested == 0 {
+        daily_stats.supply_generated = true;
+        
+        let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+        fair_launch_config.days_supply_generated = fair_launch_config.days_supply_generated.checked_add(1).unwrap();
+        
+        return pay_generate_daily_supply_bounty(ctx, day, current_time);
+    }
     
-    // Generate random supply between min and max
-    // In a real implementation, we would use a VRF (Verifiable Random Function)
-    // For simplicity, we'll use a deterministic approach based on the block hash
-    let recent_blockhash = ctx.accounts.recent_blockhashes.to_account_info().key();
-    let seed = &[
-        b"supply_generation".as_ref(),
-        &[day],
-        recent_blockhash.as_ref(),
-    ];
-    let (_, bump) = Pubkey::find_program_address(seed, ctx.program_id);
+    if daily_stats.slot_hash == [0; 32] {
+        record_slot_hash(daily_stats, &ctx.accounts.slot_hashes)?;
+    }
     
-    // Use the bump as a source of randomness (not secure for production)
-    let range = daily_stats.max_supply - daily_stats.min_supply;
-    let random_value = (bump as u64) % range;
-    let actual_supply = daily_stats.min_supply + random_value;
+    let reveal_deadline = day_end + FairLaunchConfig::SUPPLY_REVEAL_WINDOW_SECONDS;
+    let fallback = current_time > reveal_deadline;
+    let (actual_supply, randomness) = if !fallback {
+        // The seed must match the commitment made before the day opened
+        require!(
+            keccak::hash(&seed).0 == daily_stats.supply_commitment,
+            ErrorCode::InvalidReveal
+        );
+        // Mix the seed with the hash of the slot fixed at commit time, which nobody knew then
+        require!(daily_stats.slot_hash != [0; 32], ErrorCode::SupplySlotHashUnavailable);
+        
+        let randomness = derive_supply_randomness(&fair_launch_config.key(), day, &seed, &daily_stats.slot_hash);
+        daily_stats.revealed_seed = seed;
+        (
+            supply_from_randomness(daily_stats.min_supply, daily_stats.max_supply, &randomness),
+            randomness,
+        )
+    } else {
+        // The seed wasn't revealed in time, so contributions aren't locked behind the authority.
+        // The authority can compute the revealed supply once the slot hash is public, so the
+        // fallback must be worse for it than any reveal: max_supply is above every revealed supply
+        (daily_stats.max_supply, [0; 32])
+    };
     
     daily_stats.actual_supply = actual_supply;
     daily_stats.supply_generated = true;
     
-    // Calculate token allocation based on SOL invested
-    if daily_stats.total_sol_invested > 0 {
-        let tokens_per_sol = actual_supply
-            .checked_mul(1_000_000_000) // Scale for precision
-            .unwrap()
-            .checked_div(daily_stats.total_sol_invested)
-            .unwrap();
-        
-        daily_stats.total_tokens_allocated = actual_supply;
-    }
+    emit!(DailySupplyGenerated {
+        fair_launch_config: fair_launch_config.key(),
+        day,
+        revealed_seed: daily_stats.revealed_seed,
+        slot_hash_slot: daily_stats.slot_hash_slot,
+        slot_hash: daily_stats.slot_hash,
+        randomness,
+        actual_supply,
+        fallback,
+    });
     
-    Ok(())
+    // The whole day's supply is split pro-rata among the day's contributors at claim time
+    daily_stats.total_tokens_allocated = actual_supply;
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.total_tokens_allocated = fair_launch_config
+        .total_tokens_allocated
+        .checked_add(actual_supply)
+        .unwrap();
+    fair_launch_config.days_supply_generated = fair_launch_config.days_supply_generated.checked_add(1).unwrap();
+    
+    pay_generate_daily_supply_bounty(ctx, day, current_time)
 }
 
-pub fn create_liquidity(ctx: Context<CreateLiquidity>) -> Result<()> {
-    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+// Helper function to pay the keeper of generate_daily_supply and report the crank
+fn pay_generate_daily_supply_bounty(ctx: Context<GenerateDailySupply>, day: u8, current_time: i64) -> Result<()> {
+    let (bounty_lamports, bounty_tokens) = pay_keeper_bounty(
+        &mut ctx.accounts.fair_launch_config,
+        *ctx.bumps.get("fair_launch_config").unwrap(),
+        &ctx.accounts.sol_vault,
+        *ctx.bumps.get("sol_vault").unwrap(),
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.keeper,
+        ctx.accounts.keeper_token_account.as_ref(),
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
     
-    // Check if fair launch has ended
-    let current_time = Clock::get()?.unix_timestamp;
-    require!(current_time > fair_launch_config.end_time, ErrorCode::FairLaunchNotEnded);
+    emit!(LaunchCrankExecuted {
+        fair_launch_config: ctx.accounts.fair_launch_config.key(),
+        keeper: ctx.accounts.keeper.key(),
+        crank: FairLaunchConfig::CRANK_GENERATE_DAILY_SUPPLY,
+        day,
+        bounty_lamports,
+        bounty_tokens,
+        timestamp: current_time,
+    });
     
-    // Check if liquidity has already been created
-    require!(!fair_launch_config.liquidity_created, ErrorCode::LiquidityAlreadyCreated);
+    Ok(())
+}
+
+// Helper function to pay the configured keeper bounty of a launch crank
+// Each bounty is only paid while its budget covers it, so contributions and allocations are
+// never used; the token bounty is only paid if the keeper passes a token account
+#[allow(clippy::too_many_arguments)]
+fn pay_keeper_bounty<'info>(
+    fair_launch_config: &mut Account<'info, FairLaunchConfig>,
+    fair_launch_config_bump: u8,
+    sol_vault: &UncheckedAccount<'info>,
+    sol_vault_bump: u8,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    keeper: &Signer<'info>,
+    keeper_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    system_program: &Program<'info, System>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<(u64, u64)> {
+    let mut bounty_lamports = fair_launch_config.keeper_bounty_lamports;
+    if bounty_lamports > fair_launch_config.keeper_bounty_budget {
+        bounty_lamports = 0;
+    }
+    let mut bounty_tokens = fair_launch_config.keeper_bounty_tokens;
+    if keeper_token_account.is_none() || bounty_tokens > fair_launch_config.keeper_bounty_token_budget {
+        bounty_tokens = 0;
+    }
     
-    // Check if all daily supplies have been generated
-    // In a real implementation, we would check each day's stats
+    if bounty_lamports > 0 {
+        let fair_launch_config_key = fair_launch_config.key();
+        invoke_signed(
+            &system_instruction::transfer(sol_vault.key, keeper.key, bounty_lamports),
+            &[
+                sol_vault.to_account_info(),
+                keeper.to_account_info(),
+                system_program.to_account_info(),
+            ],
+            &[&[
+                b"sol_vault".as_ref(),
+                fair_launch_config_key.as_ref(),
+                &[sol_vault_bump],
+            ]],
+        )?;
+    }
     
-    // Calculate token amount for liquidity (30% of total supply)
-    let total_tokens_for_liquidity = 300_000_000 * 10u64.pow(9); // 300M tokens with 9 decimals
+    if let Some(keeper_token_account) = keeper_token_account.filter(|_| bounty_tokens > 0) {
+        let token_mint_key = fair_launch_config.token_mint;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: token_vault.to_account_info(),
+                    mint: token_mint.to_account_info(),
+                    to: keeper_token_account.to_account_info(),
+                    authority: fair_launch_config.to_account_info(),
+                },
+                &[&[
+                    b"fair_launch".as_ref(),
+                    token_mint_key.as_ref(),
+                    &fair_launch_config.round_id.to_le_bytes(),
+                    &[fair_launch_config_bump],
+                ]],
+            ),
+            bounty_tokens,
+            token_mint.decimals,
+        )?;
+    }
     
-    // Calculate SOL amount for liquidity (90% of raised SOL)
-    let sol_for_liquidity = fair_launch_config
-        .total_sol_raised
-        .checked_mul(90)
-        .unwrap()
-        .checked_div(100)
+    fair_launch_config.keeper_bounty_budget = fair_launch_config
+        .keeper_bounty_budget
+        .checked_sub(bounty_lamports)
+        .unwrap();
+    fair_launch_config.keeper_bounty_token_budget = fair_launch_config
+        .keeper_bounty_token_budget
+        .checked_sub(bounty_tokens)
+        .unwrap();
+    fair_launch_config.total_keeper_bounty_lamports = fair_launch_config
+        .total_keeper_bounty_lamports
+        .checked_add(bounty_lamports)
+        .unwrap();
+    fair_launch_config.total_keeper_bounty_tokens = fair_launch_config
+        .total_keeper_bounty_tokens
+        .checked_add(bounty_tokens)
         .unwrap();
     
-    // In a real implementation, we would:
-    // 1. Transfer tokens to the AMM (Raydium)
-    // 2. Transfer SOL to the AMM
-    // 3. Create the liquidity pool
+    Ok((bounty_lamports, bounty_tokens))
+}
+
+// Helper function to derive the randomness of a day's supply
+// Anyone can recompute it from the DailyStats and check actual_supply
+pub fn derive_supply_randomness(
+    fair_launch_config: &Pubkey,
+    day: u8,
+    seed: &[u8; 32],
+    slot_hash: &[u8; 32],
+) -> [u8; 32] {
+    keccak::hashv(&[
+        b"supply_generation".as_ref(),
+        fair_launch_config.as_ref(),
+        &[day],
+        seed,
+        slot_hash,
+    ])
+    .0
+}
+
+// Helper function to pick a supply in [min_supply, max_supply) from the randomness
+pub fn supply_from_randomness(min_supply: u64, max_supply: u64, randomness: &[u8; 32]) -> u64 {
+    let range = max_supply - min_supply;
+    let random_value = u64::from_le_bytes(randomness[..8].try_into().unwrap()) % range;
+    min_supply + random_value
+}
+
+// Helper function to store the hash of the day's supply slot, if the SlotHashes sysvar has it
+fn record_slot_hash(daily_stats: &mut DailyStats, slot_hashes: &AccountInfo) -> Result<bool> {
+    let data = slot_hashes.try_borrow_data()?;
+    match find_slot_hash(&data, daily_stats.supply_slot) {
+        Some((slot, hash)) => {
+            daily_stats.slot_hash_slot = slot;
+            daily_stats.slot_hash = hash;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// Helper function to find the SlotHashes entry of the first slot at or after target_slot
+// The sysvar is too large to deserialize on-chain, so the entries are read from the raw data.
+// Skipped slots have no entry; None if target_slot hasn't passed or has left the sysvar's history
+pub fn find_slot_hash(data: &[u8], target_slot: u64) -> Option<(u64, [u8; 32])> {
+    // Layout: entry count (u64), then (slot u64, hash [u8; 32]) entries, most recent first
+    if data.len() < 8 {
+        return None;
+    }
+    let entry_count = u64::from_le_bytes(data[0..8].try_into().unwrap()) as usize;
     
-    // For now, we'll just mark liquidity as created
-    fair_launch_config.liquidity_created = true;
+    let mut found = None;
+    for index in 0..entry_count {
+        let offset = 8 + index * 40;
+        if data.len() < offset + 40 {
+            return None;
+        }
+        let slot = u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        if slot < target_slot {
+            return found;
+        }
+        found = Some((slot, data[offset + 8..offset + 40].try_into().unwrap()));
+    }
     
-    Ok(())
+    // Every entry is at or after target_slot, so an earlier one may have left a full history
+    found.filter(|(slot, _)| *slot == target_slot || entry_count < slot_hashes::MAX_ENTRIES)
 }
 
-pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
+// Callable by anyone once the launch has ended; once liquidity exists, further calls do nothing
+pub fn create_liquidity(ctx: Context<CreateLiquidity>) -> Result<()> {
     let fair_launch_config = &ctx.accounts.fair_launch_config;
-    let investor_info = &mut ctx.accounts.investor_info;
     
-    // Check if fair launch has ended and liquidity has been created
-    require!(fair_launch_config.liquidity_created, ErrorCode::LiquidityNotCreatedYet);
+    // Check if fair launch has ended
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(current_time > fair_launch_config.end_time, ErrorCode::FairLaunchNotEnded);
+    
+    // Liquidity already created, nothing to do
+    if fair_launch_config.liquidity_created {
+        return Ok(());
+    }
+    
+    // A cancelled or failed launch can only be refunded
+    require!(!fair_launch_config.cancelled, ErrorCode::FairLaunchCancelled);
+    require!(
+        fair_launch_config.total_sol_raised >= fair_launch_config.soft_cap,
+        ErrorCode::SoftCapNotReached
+    );
     
-    // Check if investor has already claimed
-    require!(!investor_info.claimed, ErrorCode::AlreadyClaimed);
+    // Check if every day's stats exist and all daily supplies have been generated
+    require!(
+        fair_launch_config.days_initialized == fair_launch_config.investment_days,
+        ErrorCode::DailyStatsNotInitialized
+    );
+    require!(
+        fair_launch_config.days_supply_generated == fair_launch_config.investment_days,
+        ErrorCode::DailySupplyNotGenerated
+    );
     
-    // Calculate tokens to claim based on SOL invested and daily allocations
-    // In a real implementation, we would sum up allocations across all days
+    let lp_lock_seconds = fair_launch_config.lp_lock_seconds;
+    let release_governance = fair_launch_config.lp_release_governance;
     
-    // For simplicity, we'll use a fixed ratio
-    let tokens_to_claim = investor_info
-        .total_sol_invested
-        .checked_mul(fair_launch_config.total_tokens_allocated)
+    // Calculate SOL amount for liquidity (90% of raised native SOL)
+    let sol_for_liquidity = fair_launch_config
+        .total_native_sol_raised
+        .checked_mul(90)
         .unwrap()
-        .checked_div(fair_launch_config.total_sol_raised)
+        .checked_div(100)
         .unwrap();
+    require!(sol_for_liquidity > 0, ErrorCode::NoSolRaised);
+    
+    // The first round to create liquidity seeds the pool with 300M CHAD (30% of total supply),
+    // later rounds add to it in the current pool ratio, rounded up in favour of the pool
+    let amm_pool = &ctx.accounts.amm_pool;
+    let total_tokens_for_liquidity = if amm_pool.seeded {
+        require!(
+            amm_pool.sol_reserve > 0 && amm_pool.token_reserve > 0,
+            ErrorCode::PoolEmpty
+        );
+        ((sol_for_liquidity as u128) * (amm_pool.token_reserve as u128))
+            .checked_add(amm_pool.sol_reserve as u128 - 1)
+            .unwrap()
+            .checked_div(amm_pool.sol_reserve as u128)
+            .unwrap() as u64
+    } else {
+        300_000_000u64
+            .checked_mul(10u64.pow(ctx.accounts.token_mint.decimals as u32))
+            .unwrap()
+    };
     
-    // Transfer tokens to investor
-    token::transfer(
+    let fair_launch_config_key = fair_launch_config.key();
+    let token_mint_key = fair_launch_config.token_mint;
+    
+    // Move the SOL from the sol vault into the pool
+    invoke_signed(
+        &system_instruction::transfer(
+            ctx.accounts.sol_vault.key,
+            ctx.accounts.pool_sol_vault.key,
+            sol_for_liquidity,
+        ),
+        &[
+            ctx.accounts.sol_vault.to_account_info(),
+            ctx.accounts.pool_sol_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[
+            b"sol_vault".as_ref(),
+            fair_launch_config_key.as_ref(),
+            &[*ctx.bumps.get("sol_vault").unwrap()],
+        ]],
+    )?;
+    
+    // Move the tokens from the token vault into the pool
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.token_vault.to_account_info(),
-                to: ctx.accounts.investor_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.pool_token_vault.to_account_info(),
                 authority: ctx.accounts.fair_launch_config.to_account_info(),
             },
             &[&[
                 b"fair_launch".as_ref(),
-                fair_launch_config.token_mint.as_ref(),
-                &[ctx.bumps.fair_launch_config],
+                token_mint_key.as_ref(),
+                &fair_launch_config.round_id.to_le_bytes(),
+                &[*ctx.bumps.get("fair_launch_config").unwrap()],
+            ]],
+        ),
+        total_tokens_for_liquidity,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    // Only the amount that reached the pool after the transfer fee is added to the reserve
+    let transfer_fee = crate::transfer_fee::calculate_transfer_fee(
+        &ctx.accounts.token_mint.to_account_info(),
+        total_tokens_for_liquidity,
+    )?;
+    let tokens_received = total_tokens_for_liquidity.checked_sub(transfer_fee).unwrap();
+    
+    // Mint the round's LP tokens straight into its LP lock
+    let lp_amount = if amm_pool.seeded {
+        // Minted for the smaller of the two contributions, as in add_liquidity
+        let lp_supply = ctx.accounts.lp_mint.supply;
+        let lp_for_sol = (sol_for_liquidity as u128) * (lp_supply as u128) / (amm_pool.sol_reserve as u128);
+        let lp_for_tokens = (tokens_received as u128) * (lp_supply as u128) / (amm_pool.token_reserve as u128);
+        std::cmp::min(lp_for_sol, lp_for_tokens) as u64
+    } else {
+        liquidity_pool::calculate_initial_lp(sol_for_liquidity, tokens_received)
+    };
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.lp_lock_vault.to_account_info(),
+                authority: ctx.accounts.amm_pool.to_account_info(),
+            },
+            &[&[
+                b"amm_pool".as_ref(),
+                token_mint_key.as_ref(),
+                &[*ctx.bumps.get("amm_pool").unwrap()],
+            ]],
+        ),
+        lp_amount,
+    )?;
+    
+    let amm_pool = &mut ctx.accounts.amm_pool;
+    amm_pool.sol_reserve = amm_pool.sol_reserve.checked_add(sol_for_liquidity).unwrap();
+    amm_pool.token_reserve = amm_pool.token_reserve.checked_add(tokens_received).unwrap();
+    amm_pool.seeded = true;
+    
+    let lp_lock = &mut ctx.accounts.lp_lock;
+    lp_lock.authority = ctx.accounts.fair_launch_config.authority;
+    lp_lock.lp_mint = ctx.accounts.lp_mint.key();
+    lp_lock.fair_launch_config = fair_launch_config_key;
+    lp_lock.lp_vault = ctx.accounts.lp_lock_vault.key();
+    lp_lock.locked_amount = lp_amount;
+    lp_lock.locked_at = current_time;
+    lp_lock.unlock_timestamp = current_time.checked_add(lp_lock_seconds).unwrap();
+    lp_lock.governance_config = release_governance;
+    lp_lock.burned = false;
+    lp_lock.released = false;
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.liquidity_created = true;
+    fair_launch_config.claim_start_time = current_time;
+    
+    // Open trading now, which starts the decaying anti-sniper tax
+    let token_config = &mut ctx.accounts.token_config;
+    if token_config.trading_enabled_at == 0 {
+        token_config.trading_enabled_at = current_time;
+    }
+    
+    let (bounty_lamports, bounty_tokens) = pay_keeper_bounty(
+        &mut ctx.accounts.fair_launch_config,
+        *ctx.bumps.get("fair_launch_config").unwrap(),
+        &ctx.accounts.sol_vault,
+        *ctx.bumps.get("sol_vault").unwrap(),
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.keeper,
+        ctx.accounts.keeper_token_account.as_ref(),
+        &ctx.accounts.system_program,
+        &ctx.accounts.token_program,
+    )?;
+    
+    emit!(LaunchCrankExecuted {
+        fair_launch_config: ctx.accounts.fair_launch_config.key(),
+        keeper: ctx.accounts.keeper.key(),
+        crank: FairLaunchConfig::CRANK_CREATE_LIQUIDITY,
+        day: 0,
+        bounty_lamports,
+        bounty_tokens,
+        timestamp: current_time,
+    });
+    
+    Ok(())
+}
+
+// Set the LP lock used by create_liquidity, only before liquidity is created
+pub fn set_liquidity_params(
+    ctx: Context<SetLiquidityParams>,
+    lp_lock_seconds: i64,
+    release_governance: Option<Pubkey>,
+) -> Result<()> {
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    
+    require!(!fair_launch_config.liquidity_created, ErrorCode::LiquidityAlreadyCreated);
+    require!(lp_lock_seconds >= LpLock::MIN_LOCK_SECONDS, ErrorCode::LpLockTooShort);
+    
+    fair_launch_config.lp_lock_seconds = lp_lock_seconds;
+    fair_launch_config.lp_release_governance = release_governance.unwrap_or_default();
+    
+    Ok(())
+}
+
+// Set the bounty paid to the caller of each launch crank
+pub fn set_keeper_bounty(
+    ctx: Context<SetKeeperBounty>,
+    bounty_lamports: u64,
+    bounty_tokens: u64,
+) -> Result<()> {
+    require!(
+        bounty_lamports <= FairLaunchConfig::MAX_KEEPER_BOUNTY_LAMPORTS,
+        ErrorCode::KeeperBountyTooHigh
+    );
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.keeper_bounty_lamports = bounty_lamports;
+    fair_launch_config.keeper_bounty_tokens = bounty_tokens;
+    
+    Ok(())
+}
+
+// Fund the SOL keeper bounties, kept in sol_vault apart from contributions
+pub fn fund_keeper_bounties(ctx: Context<FundKeeperBounties>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidBountyFunding);
+    
+    invoke(
+        &system_instruction::transfer(
+            ctx.accounts.authority.key,
+            ctx.accounts.sol_vault.key,
+            amount,
+        ),
+        &[
+            ctx.accounts.authority.to_account_info(),
+            ctx.accounts.sol_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.keeper_bounty_budget = fair_launch_config.keeper_bounty_budget.checked_add(amount).unwrap();
+    
+    Ok(())
+}
+
+// Fund the token keeper bounties, deposited into token_vault and tracked apart from allocations
+pub fn fund_keeper_token_bounties(ctx: Context<FundKeeperTokenBounties>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidBountyFunding);
+    
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    // Only the amount that reached the vault after the transfer fee can be paid out
+    let transfer_fee = crate::transfer_fee::calculate_transfer_fee(
+        &ctx.accounts.token_mint.to_account_info(),
+        amount,
+    )?;
+    let received_amount = amount.checked_sub(transfer_fee).unwrap();
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.keeper_bounty_token_budget = fair_launch_config
+        .keeper_bounty_token_budget
+        .checked_add(received_amount)
+        .unwrap();
+    
+    Ok(())
+}
+
+// Return the unspent keeper bounty and stake bonus budgets to the authority
+// Available once liquidity exists or refunds are open; the stake bonus is turned off
+pub fn reclaim_launch_budgets(ctx: Context<ReclaimLaunchBudgets>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    if !fair_launch_config.liquidity_created {
+        check_refund_available(fair_launch_config)?;
+    }
+    
+    let budget_lamports = fair_launch_config.keeper_bounty_budget;
+    let unspent_stake_bonus = fair_launch_config
+        .stake_bonus_budget
+        .checked_sub(fair_launch_config.total_stake_bonus_paid)
+        .unwrap();
+    let budget_tokens = fair_launch_config
+        .keeper_bounty_token_budget
+        .checked_add(unspent_stake_bonus)
+        .unwrap();
+    require!(budget_lamports > 0 || budget_tokens > 0, ErrorCode::NothingToWithdraw);
+    
+    let fair_launch_config_key = fair_launch_config.key();
+    if budget_lamports > 0 {
+        invoke_signed(
+            &system_instruction::transfer(
+                ctx.accounts.sol_vault.key,
+                ctx.accounts.authority.key,
+                budget_lamports,
+            ),
+            &[
+                ctx.accounts.sol_vault.to_account_info(),
+                ctx.accounts.authority.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[&[
+                b"sol_vault".as_ref(),
+                fair_launch_config_key.as_ref(),
+                &[*ctx.bumps.get("sol_vault").unwrap()],
+            ]],
+        )?;
+    }
+    
+    if budget_tokens > 0 {
+        let token_mint_key = fair_launch_config.token_mint;
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.token_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
+                    to: ctx.accounts.authority_token_account.to_account_info(),
+                    authority: fair_launch_config.to_account_info(),
+                },
+                &[&[
+                    b"fair_launch".as_ref(),
+                    token_mint_key.as_ref(),
+                    &fair_launch_config.round_id.to_le_bytes(),
+                    &[*ctx.bumps.get("fair_launch_config").unwrap()],
+                ]],
+            ),
+            budget_tokens,
+            ctx.accounts.token_mint.decimals,
+        )?;
+    }
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.keeper_bounty_budget = 0;
+    fair_launch_config.keeper_bounty_token_budget = 0;
+    fair_launch_config.stake_bonus_budget = fair_launch_config.total_stake_bonus_paid;
+    fair_launch_config.stake_bonus_bps = 0;
+    
+    Ok(())
+}
+
+// The DailyStats of every investment day are passed as remaining accounts, in day order
+pub fn claim_tokens<'info>(ctx: Context<'_, '_, '_, 'info, ClaimTokens<'info>>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    let investor_info = &mut ctx.accounts.investor_info;
+    
+    // Check if fair launch has ended and liquidity has been created
+    require!(fair_launch_config.liquidity_created, ErrorCode::LiquidityNotCreatedYet);
+    
+    let tokens_to_claim = claimable_launch_tokens(
+        investor_info,
+        fair_launch_config,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
+    
+    // Transfer tokens to investor
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.investor_token_account.to_account_info(),
+                authority: ctx.accounts.fair_launch_config.to_account_info(),
+            },
+            &[&[
+                b"fair_launch".as_ref(),
+                fair_launch_config.token_mint.as_ref(),
+                &fair_launch_config.round_id.to_le_bytes(),
+                &[*ctx.bumps.get("fair_launch_config").unwrap()],
             ]],
         ),
         tokens_to_claim,
+        ctx.accounts.token_mint.decimals,
     )?;
     
-    // Mark as claimed
-    investor_info.claimed = true;
+    investor_info.tokens_claimed = investor_info.tokens_claimed.checked_add(tokens_to_claim).unwrap();
     
     Ok(())
 }
 
-pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
+// Claim the investor's unlocked tokens straight into the staking vault
+pub fn claim_and_stake<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAndStake<'info>>) -> Result<()> {
     let fair_launch_config = &ctx.accounts.fair_launch_config;
-    let referral_info = &mut ctx.accounts.referral_info;
+    let investor_info = &mut ctx.accounts.investor_info;
     
     // Check if fair launch has ended and liquidity has been created
     require!(fair_launch_config.liquidity_created, ErrorCode::LiquidityNotCreatedYet);
     
-    // Check if referrer has already claimed
-    require!(!referral_info.claimed, ErrorCode::AlreadyClaimed);
+    let tokens_to_claim = claimable_launch_tokens(
+        investor_info,
+        fair_launch_config,
+        ctx.remaining_accounts,
+        ctx.program_id,
+    )?;
     
-    // Calculate bonus tokens based on referred SOL
-    let bonus_tokens = referral_info
-        .total_sol_referred
-        .checked_mul(fair_launch_config.referral_bonus_bps as u64)
+    // Loyalty bonus for staking instead of claiming, paid from the funded stake bonus budget
+    // so it never uses tokens that back allocations or liquidity
+    let bonus_amount = (tokens_to_claim as u128)
+        .checked_mul(fair_launch_config.stake_bonus_bps as u128)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap() as u64;
+    require!(
+        fair_launch_config.total_stake_bonus_paid.checked_add(bonus_amount).unwrap()
+            <= fair_launch_config.stake_bonus_budget,
+        ErrorCode::StakeBonusBudgetExceeded
+    );
+    let amount = tokens_to_claim.checked_add(bonus_amount).unwrap();
+    
+    // Transfer tokens to the stake vault
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.stake_vault.to_account_info(),
+                authority: ctx.accounts.fair_launch_config.to_account_info(),
+            },
+            &[&[
+                b"fair_launch".as_ref(),
+                fair_launch_config.token_mint.as_ref(),
+                &fair_launch_config.round_id.to_le_bytes(),
+                &[*ctx.bumps.get("fair_launch_config").unwrap()],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    investor_info.tokens_claimed = investor_info.tokens_claimed.checked_add(tokens_to_claim).unwrap();
+    
+    // Only the amount that reached the vault after the transfer fee is staked
+    let transfer_fee = crate::transfer_fee::calculate_transfer_fee(
+        &ctx.accounts.token_mint.to_account_info(),
+        amount,
+    )?;
+    let received_amount = amount.checked_sub(transfer_fee).unwrap();
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    staking::add_stake(
+        &mut ctx.accounts.staking_config,
+        &mut ctx.accounts.stake_account,
+        ctx.accounts.investor.key(),
+        received_amount,
+        current_time,
+    )?;
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.total_stake_bonus_paid = fair_launch_config
+        .total_stake_bonus_paid
+        .checked_add(bonus_amount)
+        .unwrap();
+    
+    Ok(())
+}
+
+// Helper function to return the tokens an investor can claim now under the claim schedule
+// The allocation is computed on the first claim, from the DailyStats passed as remaining accounts
+fn claimable_launch_tokens(
+    investor_info: &mut InvestorInfo,
+    fair_launch_config: &Account<FairLaunchConfig>,
+    daily_stats_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<u64> {
+    if !investor_info.claimed {
+        investor_info.total_tokens_allocated = calculate_investor_allocation(
+            investor_info,
+            fair_launch_config,
+            daily_stats_accounts,
+            program_id,
+        )?;
+        investor_info.claimed = true;
+        investor_info.tokens_claimed = 0;
+    }
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    check_claim_deadline(fair_launch_config, current_time)?;
+    
+    let tokens_to_claim = calculate_claimable_amount(
+        investor_info.total_tokens_allocated,
+        investor_info.tokens_claimed,
+        fair_launch_config.claim_tge_release_bps,
+        fair_launch_config.claim_start_time,
+        fair_launch_config.claim_vesting_days as i64 * 86400,
+        0,
+        current_time,
+    )?;
+    require!(tokens_to_claim > 0, ErrorCode::NothingToClaim);
+    
+    Ok(tokens_to_claim)
+}
+
+// Helper function to sum an investor's pro-rata share of each day's supply
+// The DailyStats of every investment day are passed in day order
+fn calculate_investor_allocation(
+    investor_info: &InvestorInfo,
+    fair_launch_config: &Account<FairLaunchConfig>,
+    daily_stats_accounts: &[AccountInfo],
+    program_id: &Pubkey,
+) -> Result<u64> {
+    require!(
+        daily_stats_accounts.len() == fair_launch_config.investment_days as usize,
+        ErrorCode::MissingDailyStats
+    );
+    
+    let mut tokens_allocated: u64 = 0;
+    for (index, daily_stats_info) in daily_stats_accounts.iter().enumerate() {
+        let sol_invested = investor_info.sol_invested_per_day[index];
+        if sol_invested == 0 {
+            continue;
+        }
+        
+        let day = (index + 1) as u8;
+        let daily_stats = load_daily_stats(daily_stats_info, &fair_launch_config.key(), day, program_id)?;
+        require!(daily_stats.supply_generated, ErrorCode::SupplyNotGenerated);
+        
+        let day_allocation = calculate_day_allocation(
+            daily_stats.actual_supply,
+            daily_stats.total_sol_invested,
+            sol_invested,
+        );
+        tokens_allocated = tokens_allocated.checked_add(day_allocation).unwrap();
+    }
+    
+    Ok(tokens_allocated)
+}
+
+// Helper function to calculate an investor's share of a day's supply
+// Rounds down, so the shares of a day never add up to more than the day's supply
+pub fn calculate_day_allocation(day_supply: u64, day_sol_invested: u64, investor_sol_invested: u64) -> u64 {
+    if day_sol_invested == 0 {
+        return 0;
+    }
+    
+    ((day_supply as u128) * (investor_sol_invested as u128) / (day_sol_invested as u128)) as u64
+}
+
+// Helper function to load and verify the DailyStats account of a day
+fn load_daily_stats(
+    daily_stats_info: &AccountInfo,
+    fair_launch_config: &Pubkey,
+    day: u8,
+    program_id: &Pubkey,
+) -> Result<DailyStats> {
+    let (expected_key, _) = Pubkey::find_program_address(
+        &[b"daily_stats".as_ref(), &[day], fair_launch_config.as_ref()],
+        program_id,
+    );
+    require_keys_eq!(daily_stats_info.key(), expected_key, ErrorCode::InvalidDailyStats);
+    require_keys_eq!(*daily_stats_info.owner, *program_id, ErrorCode::InvalidDailyStats);
+    
+    let data = daily_stats_info.try_borrow_data()?;
+    DailyStats::try_deserialize(&mut &data[..])
+}
+
+// Set the claim schedule, only before liquidity is created
+pub fn set_claim_schedule(
+    ctx: Context<SetClaimSchedule>,
+    tge_release_bps: u16,
+    vesting_days: u16,
+) -> Result<()> {
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    
+    require!(!fair_launch_config.liquidity_created, ErrorCode::LiquidityAlreadyCreated);
+    require!(tge_release_bps <= 10000, ErrorCode::InvalidClaimSchedule);
+    
+    fair_launch_config.claim_tge_release_bps = tge_release_bps;
+    fair_launch_config.claim_vesting_days = vesting_days;
+    
+    // A longer schedule must still leave the minimum claim window before the deadline
+    require!(
+        fair_launch_config.claim_deadline == 0
+            || fair_launch_config.claim_deadline >= earliest_claim_deadline(fair_launch_config),
+        ErrorCode::InvalidClaimDeadline
+    );
+    
+    Ok(())
+}
+
+// Set the loyalty bonus paid on tokens staked through claim_and_stake
+pub fn set_stake_bonus(ctx: Context<SetStakeBonus>, stake_bonus_bps: u16) -> Result<()> {
+    require!(
+        stake_bonus_bps <= FairLaunchConfig::MAX_STAKE_BONUS_BPS,
+        ErrorCode::StakeBonusTooHigh
+    );
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.stake_bonus_bps = stake_bonus_bps;
+    
+    Ok(())
+}
+
+// Fund the stake bonuses, deposited into token_vault and tracked apart from allocations
+pub fn fund_stake_bonus(ctx: Context<FundStakeBonus>, amount: u64) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidBonusFunding);
+    
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.token_vault.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    // Only the amount that reached the vault after the transfer fee can be paid out
+    let transfer_fee = crate::transfer_fee::calculate_transfer_fee(
+        &ctx.accounts.token_mint.to_account_info(),
+        amount,
+    )?;
+    let received_amount = amount.checked_sub(transfer_fee).unwrap();
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.stake_bonus_budget = fair_launch_config.stake_bonus_budget.checked_add(received_amount).unwrap();
+    
+    Ok(())
+}
+
+// Set the recipients of the launch proceeds and their shares, which must add up to 100%
+pub fn set_proceeds_recipients(
+    ctx: Context<SetProceedsRecipients>,
+    marketing_wallet: Pubkey,
+    marketing_share_bps: u16,
+    team_wallet: Pubkey,
+    team_share_bps: u16,
+    treasury_wallet: Pubkey,
+    treasury_share_bps: u16,
+) -> Result<()> {
+    require!(
+        marketing_share_bps as u32 + team_share_bps as u32 + treasury_share_bps as u32 == 10000,
+        ErrorCode::InvalidProceedsSplit
+    );
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.marketing_wallet = marketing_wallet;
+    fair_launch_config.team_wallet = team_wallet;
+    fair_launch_config.treasury_wallet = treasury_wallet;
+    fair_launch_config.marketing_share_bps = marketing_share_bps;
+    fair_launch_config.team_share_bps = team_share_bps;
+    fair_launch_config.treasury_share_bps = treasury_share_bps;
+    
+    Ok(())
+}
+
+// Split the SOL left in sol_vault after liquidity creation between the proceeds recipients
+pub fn withdraw_launch_proceeds(ctx: Context<WithdrawLaunchProceeds>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    
+    require!(fair_launch_config.liquidity_created, ErrorCode::LiquidityNotCreatedYet);
+    require!(
+        fair_launch_config.marketing_share_bps as u32
+            + fair_launch_config.team_share_bps as u32
+            + fair_launch_config.treasury_share_bps as u32
+            == 10000,
+        ErrorCode::ProceedsRecipientsNotSet
+    );
+    
+    // No crank pays a bounty once liquidity exists, so the unspent keeper bounty budget is
+    // released with the proceeds; only the rent-exempt minimum stays in sol_vault
+    let amount = ctx
+        .accounts
+        .sol_vault
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(0));
+    require!(amount > 0, ErrorCode::NothingToWithdraw);
+    
+    let marketing_amount = (amount as u128)
+        .checked_mul(fair_launch_config.marketing_share_bps as u128)
         .unwrap()
         .checked_div(10000)
+        .unwrap() as u64;
+    let team_amount = (amount as u128)
+        .checked_mul(fair_launch_config.team_share_bps as u128)
         .unwrap()
-        .checked_mul(10u64.pow(9)) // Add decimals
+        .checked_div(10000)
+        .unwrap() as u64;
+    // Treasury receives the remainder, so rounding never leaves SOL behind
+    let treasury_amount = amount
+        .checked_sub(marketing_amount)
+        .unwrap()
+        .checked_sub(team_amount)
+        .unwrap();
+    
+    let fair_launch_config_key = fair_launch_config.key();
+    let sol_vault_seeds: &[&[u8]] = &[
+        b"sol_vault".as_ref(),
+        fair_launch_config_key.as_ref(),
+        &[*ctx.bumps.get("sol_vault").unwrap()],
+    ];
+    for (recipient, recipient_amount) in [
+        (&ctx.accounts.marketing_wallet, marketing_amount),
+        (&ctx.accounts.team_wallet, team_amount),
+        (&ctx.accounts.treasury_wallet, treasury_amount),
+    ] {
+        if recipient_amount == 0 {
+            continue;
+        }
+        invoke_signed(
+            &system_instruction::transfer(
+                ctx.accounts.sol_vault.key,
+                recipient.key,
+                recipient_amount,
+            ),
+            &[
+                ctx.accounts.sol_vault.to_account_info(),
+                recipient.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+            &[sol_vault_seeds],
+        )?;
+    }
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.keeper_bounty_budget = 0;
+    fair_launch_config.total_marketing_withdrawn = fair_launch_config
+        .total_marketing_withdrawn
+        .checked_add(marketing_amount)
+        .unwrap();
+    fair_launch_config.total_team_withdrawn = fair_launch_config
+        .total_team_withdrawn
+        .checked_add(team_amount)
+        .unwrap();
+    fair_launch_config.total_treasury_withdrawn = fair_launch_config
+        .total_treasury_withdrawn
+        .checked_add(treasury_amount)
         .unwrap();
     
-    // Transfer bonus tokens to referrer
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.token_vault.to_account_info(),
-                to: ctx.accounts.referrer_token_account.to_account_info(),
-                authority: ctx.accounts.fair_launch_config.to_account_info(),
-            },
-            &[&[
-                b"fair_launch".as_ref(),
-                fair_launch_config.token_mint.as_ref(),
-                &[ctx.bumps.fair_launch_config],
-            ]],
-        ),
-        bonus_tokens,
-    )?;
+    Ok(())
+}
+
+// Accept contributions in an SPL mint, valued at lamports_per_token per whole token
+pub fn add_payment_mint(
+    ctx: Context<AddPaymentMint>,
+    lamports_per_token: u64,
+    rate_fixed: bool,
+) -> Result<()> {
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    
+    require!(!fair_launch_config.liquidity_created, ErrorCode::LiquidityAlreadyCreated);
+    require!(
+        fair_launch_config.payment_mint_count < FairLaunchConfig::MAX_PAYMENT_MINTS,
+        ErrorCode::PaymentMintsFull
+    );
+    require!(lamports_per_token > 0, ErrorCode::InvalidPaymentRate);
+    
+    let payment_mint = &mut ctx.accounts.payment_mint;
+    payment_mint.fair_launch_config = fair_launch_config.key();
+    payment_mint.mint = ctx.accounts.mint.key();
+    payment_mint.vault = ctx.accounts.payment_vault.key();
+    payment_mint.index = fair_launch_config.payment_mint_count;
+    payment_mint.lamports_per_token = lamports_per_token;
+    payment_mint.rate_fixed = rate_fixed;
+    payment_mint.enabled = true;
+    payment_mint.total_contributed = 0;
+    payment_mint.total_refunded = 0;
+    payment_mint.total_withdrawn = 0;
+    
+    fair_launch_config.payment_mint_count = fair_launch_config.payment_mint_count.checked_add(1).unwrap();
+    
+    Ok(())
+}
+
+// Update the conversion rate of a payment mint or stop accepting it
+pub fn update_payment_mint(
+    ctx: Context<UpdatePaymentMint>,
+    lamports_per_token: u64,
+    enabled: bool,
+) -> Result<()> {
+    require!(lamports_per_token > 0, ErrorCode::InvalidPaymentRate);
+    
+    let payment_mint = &mut ctx.accounts.payment_mint;
+    require!(
+        !payment_mint.rate_fixed || lamports_per_token == payment_mint.lamports_per_token,
+        ErrorCode::PaymentRateFixed
+    );
+    
+    payment_mint.lamports_per_token = lamports_per_token;
+    payment_mint.enabled = enabled;
+    
+    Ok(())
+}
+
+// Split a payment mint vault between the proceeds recipients, after liquidity creation
+// The pool is CHAD/SOL, so payment mint contributions don't seed it and are paid out in full
+pub fn withdraw_payment_proceeds(ctx: Context<WithdrawPaymentProceeds>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    
+    require!(fair_launch_config.liquidity_created, ErrorCode::LiquidityNotCreatedYet);
+    require!(
+        fair_launch_config.marketing_share_bps as u32
+            + fair_launch_config.team_share_bps as u32
+            + fair_launch_config.treasury_share_bps as u32
+            == 10000,
+        ErrorCode::ProceedsRecipientsNotSet
+    );
+    
+    let amount = ctx.accounts.payment_vault.amount;
+    require!(amount > 0, ErrorCode::NothingToWithdraw);
+    
+    let marketing_amount = (amount as u128)
+        .checked_mul(fair_launch_config.marketing_share_bps as u128)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap() as u64;
+    let team_amount = (amount as u128)
+        .checked_mul(fair_launch_config.team_share_bps as u128)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap() as u64;
+    // Treasury receives the remainder, so rounding never leaves tokens behind
+    let treasury_amount = amount
+        .checked_sub(marketing_amount)
+        .unwrap()
+        .checked_sub(team_amount)
+        .unwrap();
+    
+    let token_mint_key = fair_launch_config.token_mint;
+    let fair_launch_seeds: &[&[u8]] = &[
+        b"fair_launch".as_ref(),
+        token_mint_key.as_ref(),
+        &fair_launch_config.round_id.to_le_bytes(),
+        &[*ctx.bumps.get("fair_launch_config").unwrap()],
+    ];
+    for (recipient, recipient_amount) in [
+        (&ctx.accounts.marketing_token_account, marketing_amount),
+        (&ctx.accounts.team_token_account, team_amount),
+        (&ctx.accounts.treasury_token_account, treasury_amount),
+    ] {
+        if recipient_amount == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.payment_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: recipient.to_account_info(),
+                    authority: ctx.accounts.fair_launch_config.to_account_info(),
+                },
+                &[fair_launch_seeds],
+            ),
+            recipient_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+    
+    let payment_mint = &mut ctx.accounts.payment_mint;
+    payment_mint.total_withdrawn = payment_mint.total_withdrawn.checked_add(amount).unwrap();
+    
+    Ok(())
+}
+
+// Cancel the fair launch before liquidity is created, opening refunds
+pub fn cancel_fair_launch(ctx: Context<CancelFairLaunch>) -> Result<()> {
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    
+    require!(!fair_launch_config.liquidity_created, ErrorCode::LiquidityAlreadyCreated);
+    require!(!fair_launch_config.cancelled, ErrorCode::FairLaunchCancelled);
+    
+    fair_launch_config.cancelled = true;
+    
+    Ok(())
+}
+
+// Return an investor's SOL if the launch was cancelled or missed its soft cap
+pub fn refund(ctx: Context<Refund>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    check_refund_available(fair_launch_config)?;
+    
+    let amount = ctx.accounts.investor_info.native_sol_invested;
+    require!(amount > 0, ErrorCode::NothingToRefund);
+    
+    let fair_launch_config_key = fair_launch_config.key();
+    invoke_signed(
+        &system_instruction::transfer(
+            ctx.accounts.sol_vault.key,
+            ctx.accounts.investor.key,
+            amount,
+        ),
+        &[
+            ctx.accounts.sol_vault.to_account_info(),
+            ctx.accounts.investor.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+        &[&[
+            b"sol_vault".as_ref(),
+            fair_launch_config_key.as_ref(),
+            &[*ctx.bumps.get("sol_vault").unwrap()],
+        ]],
+    )?;
+    
+    // Clear the contribution so the investor can't be refunded twice
+    let investor_info = &mut ctx.accounts.investor_info;
+    investor_info.native_sol_invested = 0;
+    
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    fair_launch_config.total_sol_refunded = fair_launch_config.total_sol_refunded.checked_add(amount).unwrap();
+    
+    Ok(())
+}
+
+// Return an investor's contribution in a payment mint, under the same conditions as refund
+pub fn refund_token(ctx: Context<RefundToken>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    check_refund_available(fair_launch_config)?;
+    
+    let index = ctx.accounts.payment_mint.index as usize;
+    let amount = ctx.accounts.investor_info.payment_mint_invested[index];
+    require!(amount > 0, ErrorCode::NothingToRefund);
+    
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.payment_vault.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                to: ctx.accounts.investor_token_account.to_account_info(),
+                authority: ctx.accounts.fair_launch_config.to_account_info(),
+            },
+            &[&[
+                b"fair_launch".as_ref(),
+                fair_launch_config.token_mint.as_ref(),
+                &fair_launch_config.round_id.to_le_bytes(),
+                &[*ctx.bumps.get("fair_launch_config").unwrap()],
+            ]],
+        ),
+        amount,
+        ctx.accounts.mint.decimals,
+    )?;
+    
+    // Clear the contribution so the investor can't be refunded twice
+    let investor_info = &mut ctx.accounts.investor_info;
+    investor_info.payment_mint_invested[index] = 0;
+    
+    let payment_mint = &mut ctx.accounts.payment_mint;
+    payment_mint.total_refunded = payment_mint.total_refunded.checked_add(amount).unwrap();
+    
+    Ok(())
+}
+
+// Return the CHAD deposited for allocations and liquidity to the authority once refunds are open
+// The unspent bounty and stake bonus budgets stay for reclaim_launch_budgets
+pub fn withdraw_unsold_tokens(ctx: Context<WithdrawUnsoldTokens>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    check_refund_available(fair_launch_config)?;
+    
+    let reserved = fair_launch_config
+        .stake_bonus_budget
+        .checked_sub(fair_launch_config.total_stake_bonus_paid)
+        .unwrap()
+        .checked_add(fair_launch_config.keeper_bounty_token_budget)
+        .unwrap();
+    let amount = ctx.accounts.token_vault.amount.saturating_sub(reserved);
+    require!(amount > 0, ErrorCode::NothingToWithdraw);
+    
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.authority_token_account.to_account_info(),
+                authority: fair_launch_config.to_account_info(),
+            },
+            &[&[
+                b"fair_launch".as_ref(),
+                fair_launch_config.token_mint.as_ref(),
+                &fair_launch_config.round_id.to_le_bytes(),
+                &[*ctx.bumps.get("fair_launch_config").unwrap()],
+            ]],
+        ),
+        amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    Ok(())
+}
+
+// Helper function to check that the launch was cancelled or missed its soft cap
+fn check_refund_available(fair_launch_config: &FairLaunchConfig) -> Result<()> {
+    require!(!fair_launch_config.liquidity_created, ErrorCode::LiquidityAlreadyCreated);
+    
+    let current_time = Clock::get()?.unix_timestamp;
+    let soft_cap_missed = current_time > fair_launch_config.end_time
+        && fair_launch_config.total_sol_raised < fair_launch_config.soft_cap;
+    require!(
+        fair_launch_config.cancelled || soft_cap_missed,
+        ErrorCode::RefundNotAvailable
+    );
+    
+    Ok(())
+}
+
+pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    let referral_info = &mut ctx.accounts.referral_info;
+    
+    // Check if fair launch has ended and liquidity has been created
+    require!(fair_launch_config.liquidity_created, ErrorCode::LiquidityNotCreatedYet);
+    
+    // Check if referrer has already claimed
+    require!(!referral_info.claimed, ErrorCode::AlreadyClaimed);
+    check_claim_deadline(fair_launch_config, Clock::get()?.unix_timestamp)?;
+    
+    let bonus_tokens = calculate_referral_bonus(fair_launch_config, referral_info);
+    
+    // Transfer bonus tokens to referrer
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.token_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.referrer_token_account.to_account_info(),
+                authority: ctx.accounts.fair_launch_config.to_account_info(),
+            },
+            &[&[
+                b"fair_launch".as_ref(),
+                fair_launch_config.token_mint.as_ref(),
+                &fair_launch_config.round_id.to_le_bytes(),
+                &[*ctx.bumps.get("fair_launch_config").unwrap()],
+            ]],
+        ),
+        bonus_tokens,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    // Mark as claimed
+    referral_info.claimed = true;
+    referral_info.total_bonus_tokens = bonus_tokens;
+    
+    Ok(())
+}
+
+// Helper function to calculate a referrer's bonus tokens based on referred SOL
+fn calculate_referral_bonus(fair_launch_config: &FairLaunchConfig, referral_info: &ReferralInfo) -> u64 {
+    referral_info
+        .total_sol_referred
+        .checked_mul(fair_launch_config.referral_bonus_bps as u64)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap()
+        .checked_mul(10u64.pow(9)) // Add decimals
+        .unwrap()
+}
+
+// Transfer a launch position to a new wallet, which then receives its claims and refunds
+// Covers moving away from a compromised key as well as OTC sales of unclaimed allocations
+pub fn transfer_investor_position(ctx: Context<TransferInvestorPosition>, new_owner: Pubkey) -> Result<()> {
+    let investor_info = &mut ctx.accounts.investor_info;
+    
+    require!(
+        new_owner != Pubkey::default() && new_owner != investor_info.investor,
+        ErrorCode::InvalidNewOwner
+    );
+    require!(investor_info.forfeited_at == 0, ErrorCode::AlreadySwept);
+    
+    let from = investor_info.investor;
+    investor_info.investor = new_owner;
+    
+    emit!(InvestorPositionTransferred {
+        fair_launch_config: ctx.accounts.fair_launch_config.key(),
+        investor_info: investor_info.key(),
+        from,
+        to: new_owner,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    Ok(())
+}
+
+// Set the claim deadline, 0 for none, and the governance that can authorize sweeps
+// Once liquidity is created the deadline can only move later
+pub fn set_claim_deadline(
+    ctx: Context<SetClaimDeadline>,
+    claim_deadline: i64,
+    sweep_governance: Option<Pubkey>,
+) -> Result<()> {
+    let fair_launch_config = &mut ctx.accounts.fair_launch_config;
+    let current_time = Clock::get()?.unix_timestamp;
+    
+    // A deadline that has passed is final, sweeps may already have happened
+    check_claim_deadline(fair_launch_config, current_time)?;
+    
+    if fair_launch_config.liquidity_created && fair_launch_config.claim_deadline != 0 {
+        require!(
+            claim_deadline > fair_launch_config.claim_deadline,
+            ErrorCode::InvalidClaimDeadline
+        );
+    }
+    require!(
+        claim_deadline == 0
+            || (claim_deadline >= earliest_claim_deadline(fair_launch_config)
+                && claim_deadline >= current_time + FairLaunchConfig::MIN_CLAIM_WINDOW_SECONDS),
+        ErrorCode::InvalidClaimDeadline
+    );
+    
+    fair_launch_config.claim_deadline = claim_deadline;
+    fair_launch_config.sweep_governance = sweep_governance.unwrap_or_default();
+    
+    Ok(())
+}
+
+// Sweep an investor's unclaimed allocation after the claim deadline, to the rewards vault or burned
+// The DailyStats of every investment day are passed as remaining accounts, in day order
+pub fn sweep_unclaimed_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, SweepUnclaimedTokens<'info>>,
+    burn: bool,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    check_sweep_available(
+        &ctx.accounts.fair_launch_config,
+        &ctx.accounts.caller.key(),
+        &ctx.accounts.proposal,
+        ctx.program_id,
+        current_time,
+    )?;
+    
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    let investor_info = &mut ctx.accounts.investor_info;
+    require!(investor_info.forfeited_at == 0, ErrorCode::AlreadySwept);
+    
+    // Investors who never claimed have no allocation computed yet
+    if !investor_info.claimed {
+        investor_info.total_tokens_allocated = calculate_investor_allocation(
+            investor_info,
+            fair_launch_config,
+            ctx.remaining_accounts,
+            ctx.program_id,
+        )?;
+        investor_info.claimed = true;
+        investor_info.tokens_claimed = 0;
+    }
+    
+    let amount = investor_info
+        .total_tokens_allocated
+        .checked_sub(investor_info.tokens_claimed)
+        .unwrap();
+    require!(amount > 0, ErrorCode::NothingToSweep);
+    
+    investor_info.tokens_forfeited = amount;
+    investor_info.forfeited_at = current_time;
+    let owner = investor_info.investor;
+    
+    sweep_from_token_vault(
+        &ctx.accounts.fair_launch_config,
+        *ctx.bumps.get("fair_launch_config").unwrap(),
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_mint,
+        &mut ctx.accounts.token_config,
+        &ctx.accounts.rewards_pool_config,
+        &ctx.accounts.rewards_vault,
+        &ctx.accounts.token_program,
+        amount,
+        burn,
+    )?;
+    
+    record_sweep(
+        &mut ctx.accounts.fair_launch_config,
+        owner,
+        false,
+        amount,
+        burn,
+        ctx.accounts.caller.key(),
+        current_time,
+    );
+    
+    Ok(())
+}
+
+// Sweep a referrer's unclaimed bonus after the claim deadline, to the rewards vault or burned
+pub fn sweep_unclaimed_referral_bonus(ctx: Context<SweepUnclaimedReferralBonus>, burn: bool) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    check_sweep_available(
+        &ctx.accounts.fair_launch_config,
+        &ctx.accounts.caller.key(),
+        &ctx.accounts.proposal,
+        ctx.program_id,
+        current_time,
+    )?;
+    
+    let fair_launch_config = &ctx.accounts.fair_launch_config;
+    let referral_info = &mut ctx.accounts.referral_info;
+    require!(referral_info.forfeited_at == 0, ErrorCode::AlreadySwept);
+    require!(!referral_info.claimed, ErrorCode::AlreadyClaimed);
+    
+    let amount = calculate_referral_bonus(fair_launch_config, referral_info);
+    require!(amount > 0, ErrorCode::NothingToSweep);
+    
+    // Closed like a claim, so the bonus can't be claimed or swept again
+    referral_info.claimed = true;
+    referral_info.bonus_forfeited = amount;
+    referral_info.forfeited_at = current_time;
+    let owner = referral_info.referrer;
+    
+    sweep_from_token_vault(
+        &ctx.accounts.fair_launch_config,
+        *ctx.bumps.get("fair_launch_config").unwrap(),
+        &ctx.accounts.token_vault,
+        &ctx.accounts.token_mint,
+        &mut ctx.accounts.token_config,
+        &ctx.accounts.rewards_pool_config,
+        &ctx.accounts.rewards_vault,
+        &ctx.accounts.token_program,
+        amount,
+        burn,
+    )?;
+    
+    record_sweep(
+        &mut ctx.accounts.fair_launch_config,
+        owner,
+        true,
+        amount,
+        burn,
+        ctx.accounts.caller.key(),
+        current_time,
+    );
+    
+    Ok(())
+}
+
+// Helper function to return the earliest allowed claim deadline under the current claim schedule
+fn earliest_claim_deadline(fair_launch_config: &FairLaunchConfig) -> i64 {
+    let claim_start_time = std::cmp::max(fair_launch_config.end_time, fair_launch_config.claim_start_time);
+    claim_start_time
+        + fair_launch_config.claim_vesting_days as i64 * 86400
+        + FairLaunchConfig::MIN_CLAIM_WINDOW_SECONDS
+}
+
+// Helper function to reject claims once the claim deadline has passed
+fn check_claim_deadline(fair_launch_config: &FairLaunchConfig, current_time: i64) -> Result<()> {
+    require!(
+        fair_launch_config.claim_deadline == 0 || current_time < fair_launch_config.claim_deadline,
+        ErrorCode::ClaimDeadlinePassed
+    );
+    
+    Ok(())
+}
+
+// Helper function to check that a sweep is due and authorized
+// The caller must be the authority, or pass an executed proposal of the sweep governance targeting this launch
+fn check_sweep_available(
+    fair_launch_config: &Account<FairLaunchConfig>,
+    caller: &Pubkey,
+    proposal: &Option<Account<Proposal>>,
+    program_id: &Pubkey,
+    current_time: i64,
+) -> Result<()> {
+    require!(fair_launch_config.liquidity_created, ErrorCode::LiquidityNotCreatedYet);
+    require!(
+        fair_launch_config.claim_deadline != 0 && current_time >= fair_launch_config.claim_deadline,
+        ErrorCode::ClaimDeadlineNotReached
+    );
+    
+    if *caller == fair_launch_config.authority {
+        return Ok(());
+    }
+    
+    require!(
+        fair_launch_config.sweep_governance != Pubkey::default(),
+        ErrorCode::UnauthorizedSweep
+    );
+    let proposal = proposal.as_ref().ok_or(ErrorCode::UnauthorizedSweep)?;
+    let (expected_proposal, _) = Pubkey::find_program_address(
+        &[
+            b"proposal".as_ref(),
+            fair_launch_config.sweep_governance.as_ref(),
+            &proposal.proposal_id.to_le_bytes(),
+        ],
+        program_id,
+    );
+    require_keys_eq!(proposal.key(), expected_proposal, ErrorCode::SweepProposalMismatch);
+    require!(proposal.status == 5, ErrorCode::SweepProposalNotExecuted); // Executed
+    require!(
+        proposal.target_program == crate::ID
+            && proposal.instruction_data == fair_launch_config.key().to_bytes().to_vec(),
+        ErrorCode::SweepProposalMismatch
+    );
+    
+    Ok(())
+}
+
+// Helper function to move forfeited tokens out of the token vault, into the rewards vault or burned
+#[allow(clippy::too_many_arguments)]
+fn sweep_from_token_vault<'info>(
+    fair_launch_config: &Account<'info, FairLaunchConfig>,
+    fair_launch_config_bump: u8,
+    token_vault: &InterfaceAccount<'info, TokenAccount>,
+    token_mint: &InterfaceAccount<'info, Mint>,
+    token_config: &mut Account<'info, TokenConfig>,
+    rewards_pool_config: &Option<Account<'info, RewardsPoolConfig>>,
+    rewards_vault: &Option<InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+    burn: bool,
+) -> Result<()> {
+    let round_id = fair_launch_config.round_id.to_le_bytes();
+    let signer_seeds: &[&[u8]] = &[
+        b"fair_launch".as_ref(),
+        fair_launch_config.token_mint.as_ref(),
+        &round_id,
+        &[fair_launch_config_bump],
+    ];
+    
+    // Forfeited allocations are recorded as penalty burns in the token's deflation stats
+    if burn {
+        return crate::burn_and_record(
+            &token_program.to_account_info(),
+            &token_mint.to_account_info(),
+            &token_vault.to_account_info(),
+            &fair_launch_config.to_account_info(),
+            &[signer_seeds],
+            token_config,
+            amount,
+            TokenConfig::BURN_SOURCE_PENALTY,
+        );
+    }
+    
+    let rewards_pool_config = rewards_pool_config.as_ref().ok_or(ErrorCode::RewardsVaultRequired)?;
+    let rewards_vault = rewards_vault.as_ref().ok_or(ErrorCode::RewardsVaultRequired)?;
+    require_keys_eq!(
+        rewards_vault.key(),
+        rewards_pool_config.rewards_vault,
+        ErrorCode::RewardsVaultRequired
+    );
+    
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: token_vault.to_account_info(),
+                mint: token_mint.to_account_info(),
+                to: rewards_vault.to_account_info(),
+                authority: fair_launch_config.to_account_info(),
+            },
+            &[signer_seeds],
+        ),
+        amount,
+        token_mint.decimals,
+    )
+}
+
+// Helper function to update the forfeiture totals and emit the sweep's audit event
+fn record_sweep(
+    fair_launch_config: &mut Account<FairLaunchConfig>,
+    owner: Pubkey,
+    referral_bonus: bool,
+    amount: u64,
+    burned: bool,
+    swept_by: Pubkey,
+    timestamp: i64,
+) {
+    fair_launch_config.total_tokens_forfeited = fair_launch_config
+        .total_tokens_forfeited
+        .checked_add(amount)
+        .unwrap();
+    if burned {
+        fair_launch_config.total_tokens_burned = fair_launch_config.total_tokens_burned.checked_add(amount).unwrap();
+    }
+    
+    emit!(UnclaimedTokensSwept {
+        fair_launch_config: fair_launch_config.key(),
+        owner,
+        referral_bonus,
+        amount,
+        burned,
+        swept_by,
+        timestamp,
+    });
+}
+
+#[derive(Accounts)]
+#[instruction(round_id: u16)]
+pub struct InitializeFairLaunch<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + FairLaunchConfig::LEN,
+        seeds = [b"fair_launch".as_ref(), token_mint.key().as_ref(), &round_id.to_le_bytes()],
+        bump
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    /// CHECK: This is a PDA that will hold SOL
+    #[account(
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        init,
+        payer = authority,
+        token::mint = token_mint,
+        token::authority = fair_launch_config,
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: u8)]
+pub struct InitDailyStats<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DailyStats::LEN,
+        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimSchedule<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetStakeBonus<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct FundStakeBonus<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        address = fair_launch_config.token_vault
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = authority,
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetProceedsRecipients<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawLaunchProceeds<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    /// CHECK: Marketing recipient set in the config
+    #[account(mut, address = fair_launch_config.marketing_wallet)]
+    pub marketing_wallet: UncheckedAccount<'info>,
+    
+    /// CHECK: Team recipient set in the config
+    #[account(mut, address = fair_launch_config.team_wallet)]
+    pub team_wallet: UncheckedAccount<'info>,
+    
+    /// CHECK: Treasury recipient set in the config
+    #[account(mut, address = fair_launch_config.treasury_wallet)]
+    pub treasury_wallet: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddPaymentMint<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PaymentMint::LEN,
+        seeds = [b"payment_mint".as_ref(), fair_launch_config.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+    
+    #[account(
+        init,
+        payer = authority,
+        seeds = [b"payment_vault".as_ref(), payment_mint.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = fair_launch_config,
+    )]
+    pub payment_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct UpdatePaymentMint<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(constraint = authority.key() == fair_launch_config.authority)]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"payment_mint".as_ref(), fair_launch_config.key().as_ref(), payment_mint.mint.as_ref()],
+        bump
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawPaymentProceeds<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"payment_mint".as_ref(), fair_launch_config.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        address = payment_mint.vault
+    )]
+    pub payment_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fair_launch_config.marketing_wallet,
+    )]
+    pub marketing_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fair_launch_config.team_wallet,
+    )]
+    pub team_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = fair_launch_config.treasury_wallet,
+    )]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RefundToken<'info> {
+    pub investor: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"investor".as_ref(), investor_info.contributor.as_ref(), fair_launch_config.key().as_ref()],
+        bump,
+        constraint = investor_info.investor == investor.key()
+    )]
+    pub investor_info: Account<'info, InvestorInfo>,
+    
+    #[account(
+        mut,
+        seeds = [b"payment_mint".as_ref(), fair_launch_config.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        address = payment_mint.vault
+    )]
+    pub payment_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = investor,
+    )]
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct CancelFairLaunch<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct Refund<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"investor".as_ref(), investor_info.contributor.as_ref(), fair_launch_config.key().as_ref()],
+        bump,
+        constraint = investor_info.investor == investor.key()
+    )]
+    pub investor_info: Account<'info, InvestorInfo>,
+    
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetWhitelist<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, day: u8, referrer: Option<Pubkey>)]
+pub struct Contribute<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    
+    #[account(mut)]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+    
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + InvestorInfo::LEN,
+        seeds = [b"investor".as_ref(), investor.key().as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub investor_info: Account<'info, InvestorInfo>,
+    
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + ReferralInfo::LEN,
+        seeds = [b"referral".as_ref(), referrer.unwrap_or_default().as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub referral_info: Option<Account<'info, ReferralInfo>>,
+    
+    /// CHECK: This is a PDA that will hold SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(amount: u64, day: u8, referrer: Option<Pubkey>)]
+pub struct ContributeToken<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    
+    #[account(mut)]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+    
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + InvestorInfo::LEN,
+        seeds = [b"investor".as_ref(), investor.key().as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub investor_info: Account<'info, InvestorInfo>,
+    
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + ReferralInfo::LEN,
+        seeds = [b"referral".as_ref(), referrer.unwrap_or_default().as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub referral_info: Option<Account<'info, ReferralInfo>>,
+    
+    #[account(
+        mut,
+        seeds = [b"payment_mint".as_ref(), fair_launch_config.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub payment_mint: Account<'info, PaymentMint>,
+    
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        address = payment_mint.vault
+    )]
+    pub payment_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = investor,
+    )]
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: u8)]
+pub struct GenerateDailySupply<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+    
+    /// CHECK: SlotHashes sysvar, read manually as it is too large to deserialize
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = token_vault.owner == fair_launch_config.key()
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    // Receives the token bounty, if any
+    #[account(
+        mut,
+        token::mint = token_mint,
+    )]
+    pub keeper_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: u8)]
+pub struct CommitDailySupply<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(constraint = authority.key() == fair_launch_config.authority)]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+}
+
+#[derive(Accounts)]
+#[instruction(day: u8)]
+pub struct RecordSupplySlotHash<'info> {
+    #[account(
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub daily_stats: Account<'info, DailyStats>,
+    
+    /// CHECK: SlotHashes sysvar, read manually as it is too large to deserialize
+    #[account(address = solana_program::sysvar::slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CreateLiquidity<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        constraint = token_vault.owner == fair_launch_config.key()
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = token_config.mint == fair_launch_config.token_mint
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        seeds = [b"amm_pool".as_ref(), fair_launch_config.token_mint.as_ref()],
+        bump
+    )]
+    pub amm_pool: Account<'info, AmmPool>,
+    
+    #[account(
+        mut,
+        address = amm_pool.token_vault
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    /// CHECK: This is a PDA that holds the pool's SOL
+    #[account(
+        mut,
+        seeds = [b"amm_sol_vault".as_ref(), amm_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_sol_vault: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        address = amm_pool.lp_mint
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+    
+    // init_if_needed so that repeated calls reach the handler and do nothing
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        space = 8 + LpLock::LEN,
+        seeds = [b"lp_lock".as_ref(), lp_mint.key().as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+    
+    // Receives the initial LP tokens, owned by the LP lock
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        seeds = [b"lp_lock_vault".as_ref(), lp_lock.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = lp_lock,
+    )]
+    pub lp_lock_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    // Receives the token bounty, if any
+    #[account(
+        mut,
+        token::mint = token_mint,
+    )]
+    pub keeper_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct SetLiquidityParams<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SetKeeperBounty<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct FundKeeperBounties<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    /// CHECK: This is a PDA that holds SOL
+    #[account(
+        mut,
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub sol_vault: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawUnsoldTokens<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        address = fair_launch_config.token_vault
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
     
-    // Mark as claimed
-    referral_info.claimed = true;
-    referral_info.total_bonus_tokens = bonus_tokens;
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = authority,
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    Ok(())
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-#[instruction(start_time: i64, investment_days: u8, referral_bonus_bps: u16)]
-pub struct InitializeFairLaunch<'info> {
+pub struct ReclaimLaunchBudgets<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, Mint>,
-    
     #[account(
-        init,
-        payer = authority,
-        space = 8 + FairLaunchConfig::LEN,
-        seeds = [b"fair_launch".as_ref(), token_mint.key().as_ref()],
-        bump
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump,
+        constraint = authority.key() == fair_launch_config.authority
     )]
     pub fair_launch_config: Account<'info, FairLaunchConfig>,
     
+    /// CHECK: This is a PDA that holds SOL
     #[account(
-        init,
-        payer = authority,
-        space = 8 + DailyStats::LEN,
-        seeds = [b"daily_stats".as_ref(), &[1u8], fair_launch_config.key().as_ref()],
+        mut,
+        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
         bump
     )]
-    pub daily_stats: Account<'info, DailyStats>,
+    pub sol_vault: UncheckedAccount<'info>,
     
-    /// CHECK: This is a PDA that will hold SOL
     #[account(
-        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
-        bump
+        mut,
+        address = fair_launch_config.token_vault
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
-        init,
-        payer = authority,
+        mut,
         token::mint = token_mint,
-        token::authority = fair_launch_config,
+        token::authority = authority,
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub rent: Sysvar<'info, Rent>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-#[instruction(amount: u64, day: u8, referrer: Option<Pubkey>)]
-pub struct Contribute<'info> {
-    #[account(mut)]
-    pub investor: Signer<'info>,
+pub struct FundKeeperTokenBounties<'info> {
+    pub authority: Signer<'info>,
     
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
     pub fair_launch_config: Account<'info, FairLaunchConfig>,
     
     #[account(
         mut,
-        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
-        bump
+        address = fair_launch_config.token_vault
     )]
-    pub daily_stats: Account<'info, DailyStats>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
-        init_if_needed,
-        payer = investor,
-        space = 8 + InvestorInfo::LEN,
-        seeds = [b"investor".as_ref(), investor.key().as_ref(), fair_launch_config.key().as_ref()],
+        mut,
+        token::mint = token_mint,
+        token::authority = authority,
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTokens<'info> {
+    #[account(mut)]
+    pub investor: Signer<'info>,
+    
+    #[account(
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
         bump
     )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"investor".as_ref(), investor_info.contributor.as_ref(), fair_launch_config.key().as_ref()],
+        bump,
+        constraint = investor_info.investor == investor.key()
+    )]
     pub investor_info: Account<'info, InvestorInfo>,
     
     #[account(
-        init_if_needed,
-        payer = investor,
-        space = 8 + ReferralInfo::LEN,
-        seeds = [b"referral".as_ref(), referrer.unwrap_or_default().as_ref(), fair_launch_config.key().as_ref()],
-        bump
+        mut,
+        constraint = token_vault.owner == fair_launch_config.key()
     )]
-    pub referral_info: Option<Account<'info, ReferralInfo>>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: This is a PDA that will hold SOL
     #[account(
         mut,
-        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
-        bump
+        constraint = investor_token_account.owner == investor.key()
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub investor_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    pub system_program: Program<'info, System>,
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-#[instruction(day: u8)]
-pub struct GenerateDailySupply<'info> {
+pub struct ClaimAndStake<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub investor: Signer<'info>,
     
+    #[account(
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
+    )]
     pub fair_launch_config: Account<'info, FairLaunchConfig>,
     
     #[account(
         mut,
-        seeds = [b"daily_stats".as_ref(), &[day], fair_launch_config.key().as_ref()],
+        seeds = [b"investor".as_ref(), investor_info.contributor.as_ref(), fair_launch_config.key().as_ref()],
+        bump,
+        constraint = investor_info.investor == investor.key()
+    )]
+    pub investor_info: Account<'info, InvestorInfo>,
+    
+    #[account(
+        mut,
+        constraint = token_vault.owner == fair_launch_config.key()
+    )]
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = staking_config.token_mint == fair_launch_config.token_mint
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = investor,
+        space = 8 + StakeAccount::LEN,
+        seeds = [b"stake_account".as_ref(), investor.key().as_ref(), staking_config.key().as_ref()],
         bump
     )]
-    pub daily_stats: Account<'info, DailyStats>,
+    pub stake_account: Account<'info, StakeAccount>,
+    
+    #[account(
+        mut,
+        token::mint = staking_config.token_mint,
+        token::authority = staking_config,
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: Used for randomness
-    pub recent_blockhashes: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct CreateLiquidity<'info> {
+pub struct ClaimReferralBonus<'info> {
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub referrer: Signer<'info>,
     
     #[account(
-        mut,
-        constraint = authority.key() == fair_launch_config.authority
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
     )]
     pub fair_launch_config: Account<'info, FairLaunchConfig>,
     
-    /// CHECK: This is a PDA that holds SOL
     #[account(
         mut,
-        seeds = [b"sol_vault".as_ref(), fair_launch_config.key().as_ref()],
-        bump
+        seeds = [b"referral".as_ref(), referrer.key().as_ref(), fair_launch_config.key().as_ref()],
+        bump,
+        constraint = referral_info.referrer == referrer.key()
     )]
-    pub sol_vault: UncheckedAccount<'info>,
+    pub referral_info: Account<'info, ReferralInfo>,
     
     #[account(
         mut,
         constraint = token_vault.owner == fair_launch_config.key()
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = referrer_token_account.owner == referrer.key()
+    )]
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    /// CHECK: This would be the Raydium AMM program in a real implementation
-    pub amm_program: UncheckedAccount<'info>,
+    #[account(address = fair_launch_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimTokens<'info> {
-    #[account(mut)]
+pub struct TransferInvestorPosition<'info> {
     pub investor: Signer<'info>,
     
     #[account(
-        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref()],
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
         bump
     )]
     pub fair_launch_config: Account<'info, FairLaunchConfig>,
     
     #[account(
         mut,
-        seeds = [b"investor".as_ref(), investor.key().as_ref(), fair_launch_config.key().as_ref()],
+        seeds = [b"investor".as_ref(), investor_info.contributor.as_ref(), fair_launch_config.key().as_ref()],
         bump,
         constraint = investor_info.investor == investor.key()
     )]
     pub investor_info: Account<'info, InvestorInfo>,
+}
+
+#[derive(Accounts)]
+pub struct SetClaimDeadline<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        constraint = authority.key() == fair_launch_config.authority
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+}
+
+#[derive(Accounts)]
+pub struct SweepUnclaimedTokens<'info> {
+    // The authority, or anyone holding an executed sweep proposal
+    pub caller: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
+        bump
+    )]
+    pub fair_launch_config: Account<'info, FairLaunchConfig>,
+    
+    #[account(
+        mut,
+        seeds = [b"investor".as_ref(), investor_info.contributor.as_ref(), fair_launch_config.key().as_ref()],
+        bump
+    )]
+    pub investor_info: Account<'info, InvestorInfo>,
     
     #[account(
         mut,
         constraint = token_vault.owner == fair_launch_config.key()
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = investor_token_account.owner == investor.key()
+        address = fair_launch_config.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = token_config.mint == fair_launch_config.token_mint
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    // Only needed when the tokens go to the rewards pool instead of being burned
+    #[account(
+        seeds = [b"rewards_pool".as_ref(), fair_launch_config.token_mint.as_ref()],
+        bump
     )]
-    pub investor_token_account: Account<'info, TokenAccount>,
+    pub rewards_pool_config: Option<Account<'info, RewardsPoolConfig>>,
+    
+    #[account(mut)]
+    pub rewards_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Executed proposal authorizing the sweep, only needed when the caller isn't the authority
+    pub proposal: Option<Account<'info, Proposal>>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
-pub struct ClaimReferralBonus<'info> {
-    #[account(mut)]
-    pub referrer: Signer<'info>,
+pub struct SweepUnclaimedReferralBonus<'info> {
+    // The authority, or anyone holding an executed sweep proposal
+    pub caller: Signer<'info>,
     
     #[account(
-        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref()],
+        mut,
+        seeds = [b"fair_launch".as_ref(), fair_launch_config.token_mint.as_ref(), &fair_launch_config.round_id.to_le_bytes()],
         bump
     )]
     pub fair_launch_config: Account<'info, FairLaunchConfig>,
     
     #[account(
         mut,
-        seeds = [b"referral".as_ref(), referrer.key().as_ref(), fair_launch_config.key().as_ref()],
-        bump,
-        constraint = referral_info.referrer == referrer.key()
+        seeds = [b"referral".as_ref(), referral_info.referrer.as_ref(), fair_launch_config.key().as_ref()],
+        bump
     )]
     pub referral_info: Account<'info, ReferralInfo>,
     
@@ -564,15 +3363,34 @@ pub struct ClaimReferralBonus<'info> {
         mut,
         constraint = token_vault.owner == fair_launch_config.key()
     )]
-    pub token_vault: Account<'info, TokenAccount>,
+    pub token_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
-        constraint = referrer_token_account.owner == referrer.key()
+        address = fair_launch_config.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(
+        mut,
+        constraint = token_config.mint == fair_launch_config.token_mint
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    // Only needed when the tokens go to the rewards pool instead of being burned
+    #[account(
+        seeds = [b"rewards_pool".as_ref(), fair_launch_config.token_mint.as_ref()],
+        bump
     )]
-    pub referrer_token_account: Account<'info, TokenAccount>,
+    pub rewards_pool_config: Option<Account<'info, RewardsPoolConfig>>,
+    
+    #[account(mut)]
+    pub rewards_vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    
+    // Executed proposal authorizing the sweep, only needed when the caller isn't the authority
+    pub proposal: Option<Account<'info, Proposal>>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[error_code]
@@ -593,8 +3411,18 @@ pub enum ErrorCode {
     DayNotYetEnded,
     #[msg("Supply already generated")]
     SupplyAlreadyGenerated,
-    #[msg("No investments for this day")]
-    NoInvestmentsForDay,
+    #[msg("Invalid supply range")]
+    InvalidSupplyRange,
+    #[msg("Supply has not been generated for every day")]
+    DailySupplyNotGenerated,
+    #[msg("Daily stats have not been initialized for every day")]
+    DailyStatsNotInitialized,
+    #[msg("Liquidity pool has no reserves")]
+    PoolEmpty,
+    #[msg("No SOL raised")]
+    NoSolRaised,
+    #[msg("LP lock duration is too short")]
+    LpLockTooShort,
     #[msg("Fair launch not ended")]
     FairLaunchNotEnded,
     #[msg("Liquidity already created")]
@@ -603,4 +3431,243 @@ pub enum ErrorCode {
     LiquidityNotCreatedYet,
     #[msg("Already claimed")]
     AlreadyClaimed,
+    #[msg("Daily stats of every investment day must be provided")]
+    MissingDailyStats,
+    #[msg("Invalid daily stats account")]
+    InvalidDailyStats,
+    #[msg("Supply not generated for a day the investor contributed to")]
+    SupplyNotGenerated,
+    #[msg("Fair launch has already started")]
+    FairLaunchAlreadyStarted,
+    #[msg("Whitelist window must end after the start and no later than the end of the fair launch")]
+    InvalidWhitelistWindow,
+    #[msg("Wallet is not whitelisted")]
+    NotWhitelisted,
+    #[msg("Whitelist cap exceeded")]
+    WhitelistCapExceeded,
+    #[msg("Soft cap must not be above the hard cap")]
+    InvalidCaps,
+    #[msg("Hard cap exceeded")]
+    HardCapExceeded,
+    #[msg("Per-wallet cap exceeded")]
+    WalletCapExceeded,
+    #[msg("Fair launch has been cancelled")]
+    FairLaunchCancelled,
+    #[msg("Soft cap not reached")]
+    SoftCapNotReached,
+    #[msg("Refunds are only available if the launch is cancelled or misses its soft cap")]
+    RefundNotAvailable,
+    #[msg("Nothing to refund")]
+    NothingToRefund,
+    #[msg("Invalid claim schedule")]
+    InvalidClaimSchedule,
+    #[msg("No tokens to claim yet")]
+    NothingToClaim,
+    #[msg("Stake bonus too high")]
+    StakeBonusTooHigh,
+    #[msg("Proceeds shares must add up to 10000 basis points")]
+    InvalidProceedsSplit,
+    #[msg("Proceeds recipients have not been set")]
+    ProceedsRecipientsNotSet,
+    #[msg("Nothing to withdraw")]
+    NothingToWithdraw,
+    #[msg("Maximum number of payment mints reached")]
+    PaymentMintsFull,
+    #[msg("Invalid payment mint conversion rate")]
+    InvalidPaymentRate,
+    #[msg("Payment mint conversion rate is fixed")]
+    PaymentRateFixed,
+    #[msg("Payment mint is disabled")]
+    PaymentMintDisabled,
+    #[msg("Day has already opened")]
+    DayAlreadyOpened,
+    #[msg("Invalid supply commitment")]
+    InvalidCommitment,
+    #[msg("Supply of the day has not been committed")]
+    SupplyNotCommitted,
+    #[msg("Revealed seed does not match the commitment")]
+    InvalidReveal,
+    #[msg("Slot hash of the day's supply is not available")]
+    SupplySlotHashUnavailable,
+    #[msg("Keeper bounty too high")]
+    KeeperBountyTooHigh,
+    #[msg("Invalid keeper bounty funding")]
+    InvalidBountyFunding,
+    #[msg("Claim deadline must leave the minimum claim window and can only be extended")]
+    InvalidClaimDeadline,
+    #[msg("Claim deadline has passed")]
+    ClaimDeadlinePassed,
+    #[msg("Claim deadline has not been reached")]
+    ClaimDeadlineNotReached,
+    #[msg("Caller is not allowed to sweep unclaimed tokens")]
+    UnauthorizedSweep,
+    #[msg("Sweep proposal has not been executed")]
+    SweepProposalNotExecuted,
+    #[msg("Proposal does not authorize sweeps for this launch")]
+    SweepProposalMismatch,
+    #[msg("Already swept")]
+    AlreadySwept,
+    #[msg("Nothing to sweep")]
+    NothingToSweep,
+    #[msg("Rewards pool vault required")]
+    RewardsVaultRequired,
+    #[msg("Position has been transferred to another wallet")]
+    PositionTransferred,
+    #[msg("Invalid new owner")]
+    InvalidNewOwner,
+    #[msg("Invalid stake bonus funding")]
+    InvalidBonusFunding,
+    #[msg("Stake bonus budget exceeded")]
+    StakeBonusBudgetExceeded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Sums each investor's share of each day, as calculate_investor_allocation does per investor
+    fn claims_per_day(day_supplies: &[u64], sol_invested_per_investor: &[Vec<u64>]) -> Vec<u64> {
+        day_supplies
+            .iter()
+            .enumerate()
+            .map(|(day, &day_supply)| {
+                let day_sol_invested: u64 = sol_invested_per_investor.iter().map(|sol| sol[day]).sum();
+                sol_invested_per_investor
+                    .iter()
+                    .map(|sol| calculate_day_allocation(day_supply, day_sol_invested, sol[day]))
+                    .sum()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn same_day_contributors_split_the_supply() {
+        let supply = 100_000_000 * 1_000_000_000;
+        let investors = vec![vec![1_000_000_000], vec![3_000_000_000], vec![6_000_000_000]];
+
+        assert_eq!(calculate_day_allocation(supply, 10_000_000_000, 1_000_000_000), supply / 10);
+        assert_eq!(calculate_day_allocation(supply, 10_000_000_000, 3_000_000_000), supply * 3 / 10);
+        assert_eq!(calculate_day_allocation(supply, 10_000_000_000, 6_000_000_000), supply * 6 / 10);
+        assert_eq!(claims_per_day(&[supply], &investors), vec![supply]);
+    }
+
+    #[test]
+    fn rounding_dust_stays_in_the_vault() {
+        // 3 equal contributors can't split 100 tokens evenly
+        let investors = vec![vec![1], vec![1], vec![1]];
+        assert_eq!(calculate_day_allocation(100, 3, 1), 33);
+        assert_eq!(claims_per_day(&[100], &investors), vec![99]);
+
+        // A contribution too small for a whole token is allocated nothing
+        let investors = vec![vec![1], vec![999_999]];
+        assert_eq!(calculate_day_allocation(10, 1_000_000, 1), 0);
+        assert_eq!(claims_per_day(&[10], &investors), vec![9]);
+    }
+
+    #[test]
+    fn days_without_contributions_allocate_nothing() {
+        assert_eq!(calculate_day_allocation(1_000_000, 0, 0), 0);
+
+        let investors = vec![vec![5, 0, 2], vec![5, 0, 0]];
+        assert_eq!(claims_per_day(&[1_000, 2_000, 3_000], &investors), vec![1_000, 0, 3_000]);
+    }
+
+    #[test]
+    fn contributor_across_several_days_gets_each_day_share() {
+        let day_supplies = [900, 600, 300];
+        let investors = vec![vec![1, 1, 1], vec![2, 0, 0], vec![0, 1, 2]];
+
+        let allocation: u64 = (0..day_supplies.len())
+            .map(|day| {
+                let day_sol_invested: u64 = investors.iter().map(|sol| sol[day]).sum();
+                calculate_day_allocation(day_supplies[day], day_sol_invested, investors[0][day])
+            })
+            .sum();
+        assert_eq!(allocation, 300 + 300 + 100);
+        assert_eq!(claims_per_day(&day_supplies, &investors), vec![900, 600, 300]);
+    }
+
+    #[test]
+    fn claims_never_exceed_day_supply() {
+        // Deterministic pseudo-random launches with uneven contributions and empty days
+        let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+        let mut next = |bound: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            seed % bound
+        };
+
+        for _ in 0..200 {
+            let days = 1 + next(7) as usize;
+            let day_supplies: Vec<u64> = (0..days).map(|_| next(1_000_000_000_000_000_000)).collect();
+            let investors: Vec<Vec<u64>> = (0..1 + next(20))
+                .map(|_| {
+                    (0..days)
+                        .map(|_| if next(3) == 0 { 0 } else { next(10_000 * 1_000_000_000) })
+                        .collect()
+                })
+                .collect();
+
+            let claims = claims_per_day(&day_supplies, &investors);
+            for (day, &claimed) in claims.iter().enumerate() {
+                assert!(claimed <= day_supplies[day]);
+                // Dust is less than one token unit per contributor
+                let contributors = investors.iter().filter(|sol| sol[day] > 0).count() as u64;
+                if contributors > 0 {
+                    assert!(day_supplies[day] - claimed < contributors);
+                }
+            }
+        }
+    }
+
+    // Builds SlotHashes sysvar data from (slot, hash byte) entries, most recent first
+    fn slot_hashes_data(entries: &[(u64, u8)]) -> Vec<u8> {
+        let mut data = (entries.len() as u64).to_le_bytes().to_vec();
+        for &(slot, hash) in entries {
+            data.extend_from_slice(&slot.to_le_bytes());
+            data.extend_from_slice(&[hash; 32]);
+        }
+        data
+    }
+
+    #[test]
+    fn finds_the_first_slot_at_or_after_the_target() {
+        let data = slot_hashes_data(&[(105, 5), (103, 3), (102, 2), (99, 1)]);
+
+        assert_eq!(find_slot_hash(&data, 102), Some((102, [2; 32])));
+        // Skipped slots are covered by the next produced slot
+        assert_eq!(find_slot_hash(&data, 100), Some((102, [2; 32])));
+        assert_eq!(find_slot_hash(&data, 104), Some((105, [5; 32])));
+        // The target hasn't been reached yet
+        assert_eq!(find_slot_hash(&data, 106), None);
+    }
+
+    #[test]
+    fn rejects_targets_that_left_a_full_history() {
+        let entries: Vec<(u64, u8)> = (0..slot_hashes::MAX_ENTRIES as u64).map(|i| (2000 - i, 7)).collect();
+        let data = slot_hashes_data(&entries);
+        let oldest = 2000 - (slot_hashes::MAX_ENTRIES as u64 - 1);
+
+        assert_eq!(find_slot_hash(&data, oldest), Some((oldest, [7; 32])));
+        assert_eq!(find_slot_hash(&data, oldest - 1), None);
+        // A short history is complete, so its oldest entry still counts
+        let data = slot_hashes_data(&[(12, 4), (10, 3)]);
+        assert_eq!(find_slot_hash(&data, 1), Some((10, [3; 32])));
+        assert_eq!(find_slot_hash(&[], 1), None);
+    }
+
+    #[test]
+    fn withholding_the_seed_gives_away_more_than_any_reveal() {
+        // The fallback is max_supply, which no revealed supply reaches, so the authority
+        // can't lower a day's supply by withholding a seed whose outcome it already knows
+        for (min_supply, max_supply) in [(100, 200), (100, 101), (0, u64::MAX), (5_000_000, 10_000_000)] {
+            for byte in [0u8, 1, 0x7f, 0x80, 0xfe, 0xff] {
+                let supply = supply_from_randomness(min_supply, max_supply, &[byte; 32]);
+                assert!(supply >= min_supply && supply < max_supply);
+            }
+            let randomness = derive_supply_randomness(&Pubkey::new_unique(), 1, &[7; 32], &[9; 32]);
+            assert!(supply_from_randomness(min_supply, max_supply, &randomness) < max_supply);
+        }
+    }
 }
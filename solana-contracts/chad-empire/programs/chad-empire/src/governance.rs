@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use solana_program::{program::invoke, system_instruction};
 use std::convert::TryInto;
 
@@ -331,7 +331,7 @@ pub struct InitializeGovernance<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
@@ -345,7 +345,7 @@ pub struct InitializeGovernance<'info> {
     #[account(
         token::mint = token_mint,
     )]
-    pub voting_token_account: Account<'info, TokenAccount>,
+    pub voting_token_account: InterfaceAccount<'info, TokenAccount>,
     
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
@@ -369,7 +369,7 @@ pub struct CreateProposal<'info> {
         token::mint = governance_config.token_mint,
         token::authority = proposer,
     )]
-    pub proposer_token_account: Account<'info, TokenAccount>,
+    pub proposer_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -402,7 +402,7 @@ pub struct CastVote<'info> {
         token::mint = governance_config.token_mint,
         token::authority = voter,
     )]
-    pub voter_token_account: Account<'info, TokenAccount>,
+    pub voter_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -431,7 +431,7 @@ pub struct FinalizeProposal<'info> {
     #[account(mut)]
     pub proposal: Account<'info, Proposal>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
 }
 
 #[derive(Accounts)]
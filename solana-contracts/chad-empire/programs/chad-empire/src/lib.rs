@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_2022::{spl_token_2022::instruction::AuthorityType, Token2022};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface};
 use solana_program::{program::invoke, system_instruction};
 use std::convert::TryInto;
 
@@ -11,6 +12,10 @@ mod referral;
 mod vesting;
 mod rewards_pool;
 mod governance;
+mod transfer_fee;
+mod token_metadata;
+mod liquidity_pool;
+mod lp_lock;
 
 // Re-export the modules
 pub use fair_launch::*;
@@ -20,6 +25,10 @@ pub use referral::*;
 pub use vesting::*;
 pub use rewards_pool::*;
 pub use governance::*;
+pub use transfer_fee::*;
+pub use token_metadata::*;
+pub use liquidity_pool::*;
+pub use lp_lock::*;
 
 declare_id!("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
 
@@ -29,63 +38,137 @@ pub mod chad_empire {
 
     // CHAD Token Contract
     // Initialize the CHAD token with a fixed supply of 1 billion tokens
-    pub fn initialize_token(ctx: Context<InitializeToken>, decimals: u8) -> Result<()> {
+    pub fn initialize_token(
+        ctx: Context<InitializeToken>,
+        decimals: u8,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let metadata = token_metadata::token_data(name, symbol, uri)?;
+
         // Set up the token mint with the authority
         let total_supply = 1_000_000_000; // 1 billion tokens
         let decimals_multiplier = 10u64.pow(decimals as u32);
         let total_supply_with_decimals = total_supply * decimals_multiplier;
 
-        // Create the token mint
-        token::initialize_mint(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                token::InitializeMint {
-                    mint: ctx.accounts.mint.to_account_info(),
-                    rent: ctx.accounts.rent.to_account_info(),
-                },
-            ),
+        let buy_tax_bps = 1000; // 10% buy tax (basis points)
+        let sell_tax_bps = 1000; // 10% sell tax (basis points)
+        
+        // The mint-level fee taxes transfers that don't go through the program, like plain SPL
+        // transfers and swaps on external DEXes. Token-2022 charges one rate on every transfer
+        // and can't tell a buy from a sell, so it is the lower of the two tax rates.
+        // transfer_with_tax and the pool release it and charge the exact buy or sell rate instead
+        let transfer_fee_bps = std::cmp::min(buy_tax_bps, sell_tax_bps);
+
+        // Create the Token-2022 mint with the transfer fee extension
+        transfer_fee::create_fee_mint(
+            &ctx.accounts.authority,
+            &ctx.accounts.mint,
+            &ctx.accounts.fee_authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
             decimals,
-            ctx.accounts.authority.key,
-            Some(ctx.accounts.authority.key),
+            transfer_fee_bps,
         )?;
 
-        // Initialize the rewards pool account
-        token::initialize_account(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                token::InitializeAccount {
-                    account: ctx.accounts.rewards_pool.to_account_info(),
-                    mint: ctx.accounts.mint.to_account_info(),
-                    authority: ctx.accounts.authority.to_account_info(),
-                    rent: ctx.accounts.rent.to_account_info(),
-                },
-            ),
+        // Create the authority token account and the rewards pool account
+        transfer_fee::create_fee_token_account(
+            &ctx.accounts.authority,
+            &ctx.accounts.authority_token_account,
+            &ctx.accounts.mint,
+            &ctx.accounts.authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
+        )?;
+        transfer_fee::create_fee_token_account(
+            &ctx.accounts.authority,
+            &ctx.accounts.rewards_pool,
+            &ctx.accounts.mint,
+            &ctx.accounts.authority,
+            &ctx.accounts.token_program,
+            &ctx.accounts.system_program,
         )?;
 
         // Mint the total supply to the authority
-        token::mint_to(
-            CpiContext::new_with_signer(
+        token_interface::mint_to(
+            CpiContext::new(
                 ctx.accounts.token_program.to_account_info(),
-                token::MintTo {
+                token_interface::MintTo {
                     mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.authority_token_account.to_account_info(),
                     authority: ctx.accounts.authority.to_account_info(),
                 },
-                &[],
             ),
             total_supply_with_decimals,
         )?;
 
+        // Create the Metaplex metadata account so wallets show the name, symbol and logo
+        token_metadata::create_metadata_account(
+            &ctx.accounts.metadata,
+            &ctx.accounts.mint,
+            &ctx.accounts.authority,
+            &ctx.accounts.authority,
+            &ctx.accounts.metadata_authority,
+            &ctx.accounts.metadata_program,
+            &ctx.accounts.system_program,
+            &ctx.accounts.rent,
+            metadata,
+        )?;
+
+        // Record the supply as reported by the mint, so it can be verified on-chain
+        let minted_supply = transfer_fee::get_mint_supply(&ctx.accounts.mint.to_account_info())?;
+        require!(minted_supply == total_supply_with_decimals, ErrorCode::SupplyMismatch);
+
         // Initialize the token config account
         let token_config = &mut ctx.accounts.token_config;
         token_config.authority = ctx.accounts.authority.key();
         token_config.mint = ctx.accounts.mint.key();
         token_config.rewards_pool = ctx.accounts.rewards_pool.key();
-        token_config.buy_tax_bps = 1000; // 10% buy tax (basis points)
-        token_config.sell_tax_bps = 1000; // 10% sell tax (basis points)
+        token_config.buy_tax_bps = buy_tax_bps;
+        token_config.sell_tax_bps = sell_tax_bps;
         token_config.minting_locked = false;
-        token_config.total_supply = total_supply_with_decimals;
+        token_config.total_supply = minted_supply;
         token_config.decimals = decimals;
+        token_config.transfer_fee_bps = transfer_fee_bps;
+        token_config.total_fees_harvested = 0;
+        token_config.registered_pools = 0;
+
+        // All tax goes to the rewards pool until a split is configured
+        token_config.liquidity_accumulator = ctx.accounts.rewards_pool.key();
+        token_config.treasury = ctx.accounts.rewards_pool.key();
+        token_config.rewards_tax_share_bps = 10000;
+        token_config.burn_tax_share_bps = 0;
+        token_config.liquidity_tax_share_bps = 0;
+        token_config.treasury_tax_share_bps = 0;
+        token_config.total_tax_to_rewards = 0;
+        token_config.total_tax_burned = 0;
+        token_config.total_tax_to_liquidity = 0;
+        token_config.total_tax_to_treasury = 0;
+
+        // No transfer limits until configured
+        token_config.max_tx_amount = 0;
+        token_config.max_wallet_balance = 0;
+        token_config.transfer_limits_lifted = false;
+
+        // Trading opens when the fair launch creates liquidity
+        token_config.trading_enabled_at = 0;
+        token_config.launch_tax_bps = TokenConfig::DEFAULT_LAUNCH_TAX_BPS;
+        token_config.launch_tax_decay_seconds = TokenConfig::DEFAULT_LAUNCH_TAX_DECAY_SECONDS;
+
+        token_config.metadata_locked = false;
+
+        // Nothing has been burned yet
+        token_config.circulating_supply = minted_supply;
+        token_config.total_user_burned = 0;
+        token_config.total_penalty_burned = 0;
+        token_config.total_buyback_burned = 0;
+
+        // No tax change scheduled
+        token_config.pending_buy_tax_bps = 0;
+        token_config.pending_sell_tax_bps = 0;
+        token_config.pending_transfer_fee_bps = 0;
+        token_config.pending_tax_effective_at = 0;
 
         Ok(())
     }
@@ -97,29 +180,62 @@ pub mod chad_empire {
             ErrorCode::Unauthorized
         );
 
+        require!(!ctx.accounts.token_config.minting_locked, ErrorCode::MintingAlreadyLocked);
+
+        // Revoke the mint authority so no more tokens can ever be minted
+        token_interface::set_authority(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            AuthorityType::MintTokens,
+            None,
+        )?;
+
+        // Revoke the freeze authority so holder accounts can never be frozen
+        token_interface::set_authority(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token_interface::SetAuthority {
+                    current_authority: ctx.accounts.authority.to_account_info(),
+                    account_or_mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            AuthorityType::FreezeAccount,
+            None,
+        )?;
+
+        // The supply at lock time is final
         let token_config = &mut ctx.accounts.token_config;
         token_config.minting_locked = true;
+        token_config.total_supply = ctx.accounts.mint.supply;
 
         Ok(())
     }
 
     // Transfer tokens with tax
     pub fn transfer_with_tax(ctx: Context<TransferWithTax>, amount: u64) -> Result<()> {
-        let token_config = &ctx.accounts.token_config;
-        
         // Determine if this is a buy or sell transaction
-        // For simplicity, we'll consider transfers to the rewards pool as sells
-        // and transfers from the rewards pool as buys
-        let is_sell = ctx.accounts.to_token_account.key() == token_config.rewards_pool;
-        let is_buy = ctx.accounts.from_token_account.key() == token_config.rewards_pool;
+        // Transfers out of a registered AMM pool are buys, transfers into one are sells
+        let is_sell = LiquidityPoolEntry::is_registered(&ctx.accounts.to_pool_entry);
+        let is_buy = LiquidityPoolEntry::is_registered(&ctx.accounts.from_pool_entry);
         
-        let tax_bps = if is_sell {
-            token_config.sell_tax_bps
-        } else if is_buy {
-            token_config.buy_tax_bps
-        } else {
-            0 // No tax for regular transfers between users
-        };
+        // Protocol vaults and partners on the exemption list are not taxed
+        let is_exempt = ctx.accounts.from_exemption.is_some() || ctx.accounts.to_exemption.is_some();
+        
+        // Check the trading gate and anti-whale limits, and get the buy/sell tax rate
+        let current_time = Clock::get()?.unix_timestamp;
+        let tax_bps = ctx.accounts.token_config.transfer_tax_bps(
+            amount,
+            ctx.accounts.to_token_account.amount,
+            is_buy,
+            is_sell,
+            is_exempt,
+            current_time,
+        )?;
         
         // Calculate tax amount
         let tax_amount = (amount as u128)
@@ -130,42 +246,49 @@ pub mod chad_empire {
         
         let transfer_amount = amount.checked_sub(tax_amount).unwrap();
         
-        // Transfer tax to rewards pool if applicable
-        if tax_amount > 0 {
-            token::transfer(
-                CpiContext::new(
-                    ctx.accounts.token_program.to_account_info(),
-                    Transfer {
-                        from: ctx.accounts.from_token_account.to_account_info(),
-                        to: ctx.accounts.rewards_pool.to_account_info(),
-                        authority: ctx.accounts.authority.to_account_info(),
-                    },
-                ),
-                tax_amount,
-            )?;
-        }
+        // Split the tax between rewards, burn, auto-liquidity and treasury
+        distribute_tax(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.from_token_account.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &[],
+            &ctx.accounts.rewards_pool.to_account_info(),
+            &ctx.accounts.liquidity_accumulator.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.fee_authority.to_account_info(),
+            *ctx.bumps.get("fee_authority").unwrap(),
+            &mut ctx.accounts.token_config,
+            tax_amount,
+            ctx.accounts.mint.decimals,
+        )?;
         
         // Transfer remaining amount to recipient
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                Transfer {
-                    from: ctx.accounts.from_token_account.to_account_info(),
-                    to: ctx.accounts.to_token_account.to_account_info(),
-                    authority: ctx.accounts.authority.to_account_info(),
-                },
-            ),
+        // The tax above is the whole charge, so the mint's transfer fee is not added on top:
+        // regular and exempt transfers stay untaxed, buys and sells pay exactly their rate
+        transfer_fee::transfer_checked_without_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.from_token_account.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.to_token_account.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &[],
+            &ctx.accounts.fee_authority.to_account_info(),
+            *ctx.bumps.get("fee_authority").unwrap(),
             transfer_amount,
+            ctx.accounts.mint.decimals,
         )?;
         
         Ok(())
     }
 
-    // Update tax rates (only callable by authority)
+    // Schedule new tax rates and mint transfer fee (only callable by authority)
+    // The rates are applied by apply_tax_rates once the timelock has passed
     pub fn update_tax_rates(
         ctx: Context<UpdateTaxRates>,
         new_buy_tax_bps: u16,
         new_sell_tax_bps: u16,
+        new_transfer_fee_bps: u16,
     ) -> Result<()> {
         require!(
             ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
@@ -176,21 +299,312 @@ pub mod chad_empire {
         require!(new_buy_tax_bps <= 2000, ErrorCode::TaxTooHigh);
         require!(new_sell_tax_bps <= 2000, ErrorCode::TaxTooHigh);
         
+        // The mint-level fee is a floor for untracked venues, never above the lower tax rate
+        require!(
+            new_transfer_fee_bps <= std::cmp::min(new_buy_tax_bps, new_sell_tax_bps),
+            ErrorCode::TransferFeeTooHigh
+        );
+        
+        let token_config = &mut ctx.accounts.token_config;
+        
+        // Each change may only move the rates by a limited step from the active rates
+        require!(
+            new_buy_tax_bps.abs_diff(token_config.buy_tax_bps) <= TokenConfig::MAX_TAX_STEP_BPS,
+            ErrorCode::TaxStepTooLarge
+        );
+        require!(
+            new_sell_tax_bps.abs_diff(token_config.sell_tax_bps) <= TokenConfig::MAX_TAX_STEP_BPS,
+            ErrorCode::TaxStepTooLarge
+        );
+        
+        // Scheduling again replaces the pending rates and restarts the timelock
+        let current_time = Clock::get()?.unix_timestamp;
+        token_config.pending_buy_tax_bps = new_buy_tax_bps;
+        token_config.pending_sell_tax_bps = new_sell_tax_bps;
+        token_config.pending_transfer_fee_bps = new_transfer_fee_bps;
+        token_config.pending_tax_effective_at = current_time
+            .checked_add(TokenConfig::TAX_CHANGE_DELAY_SECONDS)
+            .unwrap();
+        
+        Ok(())
+    }
+    
+    // Apply pending tax rates once their timelock has passed (callable by anyone)
+    pub fn apply_tax_rates(ctx: Context<ApplyTaxRates>) -> Result<()> {
+        let token_config = &ctx.accounts.token_config;
+        require!(token_config.pending_tax_effective_at > 0, ErrorCode::NoPendingTaxChange);
+        
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            current_time >= token_config.pending_tax_effective_at,
+            ErrorCode::TaxChangeTimelocked
+        );
+        
+        let new_buy_tax_bps = token_config.pending_buy_tax_bps;
+        let new_sell_tax_bps = token_config.pending_sell_tax_bps;
+        
+        // Token-2022 applies the new mint-level fee two epochs later; until then the mint
+        // keeps charging the older fee, which calculate_transfer_fee reads from the mint itself
+        let transfer_fee_bps = token_config.pending_transfer_fee_bps;
+        transfer_fee::set_mint_transfer_fee(
+            &ctx.accounts.mint,
+            &ctx.accounts.fee_authority,
+            *ctx.bumps.get("fee_authority").unwrap(),
+            &ctx.accounts.token_program,
+            transfer_fee_bps,
+        )?;
+        
         let token_config = &mut ctx.accounts.token_config;
         token_config.buy_tax_bps = new_buy_tax_bps;
         token_config.sell_tax_bps = new_sell_tax_bps;
+        token_config.transfer_fee_bps = transfer_fee_bps;
+        token_config.pending_buy_tax_bps = 0;
+        token_config.pending_sell_tax_bps = 0;
+        token_config.pending_transfer_fee_bps = 0;
+        token_config.pending_tax_effective_at = 0;
+        
+        Ok(())
+    }
+    
+    // Update how collected tax is split between destinations (only callable by authority)
+    pub fn update_tax_split(
+        ctx: Context<UpdateTaxSplit>,
+        rewards_tax_share_bps: u16,
+        burn_tax_share_bps: u16,
+        liquidity_tax_share_bps: u16,
+        treasury_tax_share_bps: u16,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        // Ensure the shares add up to 100%
+        require!(
+            rewards_tax_share_bps as u32
+                + burn_tax_share_bps as u32
+                + liquidity_tax_share_bps as u32
+                + treasury_tax_share_bps as u32
+                == 10000,
+            ErrorCode::InvalidTaxSplit
+        );
+        
+        let token_config = &mut ctx.accounts.token_config;
+        token_config.liquidity_accumulator = ctx.accounts.liquidity_accumulator.key();
+        token_config.treasury = ctx.accounts.treasury.key();
+        token_config.rewards_tax_share_bps = rewards_tax_share_bps;
+        token_config.burn_tax_share_bps = burn_tax_share_bps;
+        token_config.liquidity_tax_share_bps = liquidity_tax_share_bps;
+        token_config.treasury_tax_share_bps = treasury_tax_share_bps;
+        
+        Ok(())
+    }
+    
+    // Burn tokens from the signer's account, recorded as a user burn
+    // Tax, penalty and buyback burns are only recorded by the program paths that produce them
+    pub fn burn_tokens(ctx: Context<BurnTokens>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidBurnAmount);
+        
+        burn_and_record(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.mint.to_account_info(),
+            &ctx.accounts.from_token_account.to_account_info(),
+            &ctx.accounts.authority.to_account_info(),
+            &[],
+            &mut ctx.accounts.token_config,
+            amount,
+            TokenConfig::BURN_SOURCE_USER,
+        )
+    }
+    
+    // Update the anti-whale limits, 0 disables a limit (only callable by authority)
+    pub fn update_transfer_limits(
+        ctx: Context<UpdateTransferLimits>,
+        max_tx_amount: u64,
+        max_wallet_balance: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let token_config = &mut ctx.accounts.token_config;
+        require!(!token_config.transfer_limits_lifted, ErrorCode::TransferLimitsLifted);
+        
+        token_config.max_tx_amount = max_tx_amount;
+        token_config.max_wallet_balance = max_wallet_balance;
+        
+        Ok(())
+    }
+    
+    // Permanently lift the anti-whale limits
+    pub fn lift_transfer_limits(ctx: Context<UpdateTransferLimits>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let token_config = &mut ctx.accounts.token_config;
+        token_config.transfer_limits_lifted = true;
+        token_config.max_tx_amount = 0;
+        token_config.max_wallet_balance = 0;
+        
+        Ok(())
+    }
+    
+    // Configure the anti-sniper tax applied to buys right after trading opens
+    pub fn update_launch_tax(
+        ctx: Context<UpdateLaunchTax>,
+        launch_tax_bps: u16,
+        launch_tax_decay_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let token_config = &mut ctx.accounts.token_config;
+        
+        // The schedule is fixed once trading has been opened
+        require!(token_config.trading_enabled_at == 0, ErrorCode::TradingAlreadyEnabled);
+        require!(launch_tax_bps <= TokenConfig::MAX_LAUNCH_TAX_BPS, ErrorCode::TaxTooHigh);
+        require!(launch_tax_decay_seconds >= 0, ErrorCode::InvalidLaunchTaxDecay);
+        
+        token_config.launch_tax_bps = launch_tax_bps;
+        token_config.launch_tax_decay_seconds = launch_tax_decay_seconds;
         
         Ok(())
     }
     
+    // Register an AMM pool token account used to classify buys and sells
+    pub fn add_liquidity_pool(ctx: Context<AddLiquidityPool>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let token_config = &mut ctx.accounts.token_config;
+        require!(
+            token_config.registered_pools < TokenConfig::MAX_REGISTERED_POOLS,
+            ErrorCode::PoolRegistryFull
+        );
+        
+        let pool_entry = &mut ctx.accounts.pool_entry;
+        pool_entry.token_config = token_config.key();
+        pool_entry.pool_token_account = ctx.accounts.pool_token_account.key();
+        pool_entry.added_at = Clock::get()?.unix_timestamp;
+        
+        token_config.registered_pools = token_config.registered_pools.checked_add(1).unwrap();
+        
+        Ok(())
+    }
+    
+    // Remove a pool from the registry, transfers through it are no longer taxed as buys/sells
+    pub fn remove_liquidity_pool(ctx: Context<RemoveLiquidityPool>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let token_config = &mut ctx.accounts.token_config;
+        token_config.registered_pools = token_config.registered_pools.checked_sub(1).unwrap();
+        
+        Ok(())
+    }
+    
+    // Exempt a token account, or every account of an owner, from transfer_with_tax
+    pub fn add_tax_exemption(ctx: Context<AddTaxExemption>, exempt_key: Pubkey) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        let current_time = Clock::get()?.unix_timestamp;
+        let tax_exemption = &mut ctx.accounts.tax_exemption;
+        tax_exemption.token_config = ctx.accounts.token_config.key();
+        tax_exemption.exempt_key = exempt_key;
+        tax_exemption.added_at = current_time;
+        
+        emit!(TaxExemptionUpdated {
+            token_config: ctx.accounts.token_config.key(),
+            exempt_key,
+            exempt: true,
+            timestamp: current_time,
+        });
+        
+        Ok(())
+    }
+    
+    // Remove an exemption, closing its account
+    pub fn remove_tax_exemption(ctx: Context<RemoveTaxExemption>) -> Result<()> {
+        require!(
+            ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+            ErrorCode::Unauthorized
+        );
+        
+        emit!(TaxExemptionUpdated {
+            token_config: ctx.accounts.token_config.key(),
+            exempt_key: ctx.accounts.tax_exemption.exempt_key,
+            exempt: false,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        
+        Ok(())
+    }
+    
+    // Update the token name, symbol and URI (only callable by authority, until locked)
+    pub fn update_token_metadata(
+        ctx: Context<UpdateTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        token_metadata::update_token_metadata(ctx, name, symbol, uri)
+    }
+    
+    // Permanently lock the token metadata
+    pub fn lock_token_metadata(ctx: Context<UpdateTokenMetadata>) -> Result<()> {
+        token_metadata::lock_token_metadata(ctx)
+    }
+    
+    // Sweep withheld transfer fees into the rewards pool (callable by anyone)
+    pub fn harvest_transfer_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, HarvestTransferFees<'info>>,
+    ) -> Result<()> {
+        transfer_fee::harvest_transfer_fees(ctx)
+    }
+    
     // Fair Launch Contract
+    #[allow(clippy::too_many_arguments)]
     pub fn initialize_fair_launch(
         ctx: Context<InitializeFairLaunch>,
+        round_id: u16,
         start_time: i64,
         investment_days: u8,
         referral_bonus_bps: u16,
+        soft_cap: u64,
+        hard_cap: u64,
+        max_per_wallet: u64,
     ) -> Result<()> {
-        fair_launch::initialize_fair_launch(ctx, start_time, investment_days, referral_bonus_bps)
+        fair_launch::initialize_fair_launch(
+            ctx,
+            round_id,
+            start_time,
+            investment_days,
+            referral_bonus_bps,
+            soft_cap,
+            hard_cap,
+            max_per_wallet,
+        )
+    }
+    
+    pub fn init_daily_stats(
+        ctx: Context<InitDailyStats>,
+        day: u8,
+        min_supply: u64,
+        max_supply: u64,
+    ) -> Result<()> {
+        fair_launch::init_daily_stats(ctx, day, min_supply, max_supply)
     }
     
     pub fn contribute(
@@ -198,24 +612,229 @@ pub mod chad_empire {
         amount: u64,
         day: u8,
         referrer: Option<Pubkey>,
+        whitelist_cap: u64,
+        whitelist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        fair_launch::contribute(ctx, amount, day, referrer, whitelist_cap, whitelist_proof)
+    }
+    
+    pub fn contribute_token(
+        ctx: Context<ContributeToken>,
+        amount: u64,
+        day: u8,
+        referrer: Option<Pubkey>,
+        whitelist_cap: u64,
+        whitelist_proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        fair_launch::contribute_token(ctx, amount, day, referrer, whitelist_cap, whitelist_proof)
+    }
+    
+    pub fn add_payment_mint(
+        ctx: Context<AddPaymentMint>,
+        lamports_per_token: u64,
+        rate_fixed: bool,
+    ) -> Result<()> {
+        fair_launch::add_payment_mint(ctx, lamports_per_token, rate_fixed)
+    }
+    
+    pub fn update_payment_mint(
+        ctx: Context<UpdatePaymentMint>,
+        lamports_per_token: u64,
+        enabled: bool,
+    ) -> Result<()> {
+        fair_launch::update_payment_mint(ctx, lamports_per_token, enabled)
+    }
+    
+    pub fn set_whitelist(
+        ctx: Context<SetWhitelist>,
+        whitelist_root: [u8; 32],
+        whitelist_end_time: i64,
+    ) -> Result<()> {
+        fair_launch::set_whitelist(ctx, whitelist_root, whitelist_end_time)
+    }
+    
+    pub fn commit_daily_supply(ctx: Context<CommitDailySupply>, day: u8, commitment: [u8; 32]) -> Result<()> {
+        fair_launch::commit_daily_supply(ctx, day, commitment)
+    }
+    
+    pub fn record_supply_slot_hash(ctx: Context<RecordSupplySlotHash>, day: u8) -> Result<()> {
+        fair_launch::record_supply_slot_hash(ctx, day)
+    }
+    
+    pub fn generate_daily_supply(ctx: Context<GenerateDailySupply>, day: u8, seed: [u8; 32]) -> Result<()> {
+        fair_launch::generate_daily_supply(ctx, day, seed)
+    }
+    
+    pub fn create_liquidity(ctx: Context<CreateLiquidity>) -> Result<()> {
+        fair_launch::create_liquidity(ctx)
+    }
+    
+    pub fn set_liquidity_params(
+        ctx: Context<SetLiquidityParams>,
+        lp_lock_seconds: i64,
+        release_governance: Option<Pubkey>,
+    ) -> Result<()> {
+        fair_launch::set_liquidity_params(ctx, lp_lock_seconds, release_governance)
+    }
+    
+    pub fn set_keeper_bounty(
+        ctx: Context<SetKeeperBounty>,
+        bounty_lamports: u64,
+        bounty_tokens: u64,
+    ) -> Result<()> {
+        fair_launch::set_keeper_bounty(ctx, bounty_lamports, bounty_tokens)
+    }
+    
+    pub fn fund_keeper_bounties(ctx: Context<FundKeeperBounties>, amount: u64) -> Result<()> {
+        fair_launch::fund_keeper_bounties(ctx, amount)
+    }
+    
+    pub fn fund_keeper_token_bounties(ctx: Context<FundKeeperTokenBounties>, amount: u64) -> Result<()> {
+        fair_launch::fund_keeper_token_bounties(ctx, amount)
+    }
+    
+    pub fn withdraw_unsold_tokens(ctx: Context<WithdrawUnsoldTokens>) -> Result<()> {
+        fair_launch::withdraw_unsold_tokens(ctx)
+    }
+    
+    pub fn reclaim_launch_budgets(ctx: Context<ReclaimLaunchBudgets>) -> Result<()> {
+        fair_launch::reclaim_launch_budgets(ctx)
+    }
+    
+    pub fn claim_tokens<'info>(ctx: Context<'_, '_, '_, 'info, ClaimTokens<'info>>) -> Result<()> {
+        fair_launch::claim_tokens(ctx)
+    }
+    
+    pub fn claim_and_stake<'info>(ctx: Context<'_, '_, '_, 'info, ClaimAndStake<'info>>) -> Result<()> {
+        fair_launch::claim_and_stake(ctx)
+    }
+    
+    pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
+        fair_launch::claim_referral_bonus(ctx)
+    }
+    
+    pub fn transfer_investor_position(ctx: Context<TransferInvestorPosition>, new_owner: Pubkey) -> Result<()> {
+        fair_launch::transfer_investor_position(ctx, new_owner)
+    }
+    
+    pub fn set_claim_deadline(
+        ctx: Context<SetClaimDeadline>,
+        claim_deadline: i64,
+        sweep_governance: Option<Pubkey>,
+    ) -> Result<()> {
+        fair_launch::set_claim_deadline(ctx, claim_deadline, sweep_governance)
+    }
+    
+    pub fn sweep_unclaimed_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, SweepUnclaimedTokens<'info>>,
+        burn: bool,
+    ) -> Result<()> {
+        fair_launch::sweep_unclaimed_tokens(ctx, burn)
+    }
+    
+    pub fn sweep_unclaimed_referral_bonus(ctx: Context<SweepUnclaimedReferralBonus>, burn: bool) -> Result<()> {
+        fair_launch::sweep_unclaimed_referral_bonus(ctx, burn)
+    }
+    
+    pub fn set_claim_schedule(
+        ctx: Context<SetClaimSchedule>,
+        tge_release_bps: u16,
+        vesting_days: u16,
+    ) -> Result<()> {
+        fair_launch::set_claim_schedule(ctx, tge_release_bps, vesting_days)
+    }
+    
+    pub fn set_stake_bonus(ctx: Context<SetStakeBonus>, stake_bonus_bps: u16) -> Result<()> {
+        fair_launch::set_stake_bonus(ctx, stake_bonus_bps)
+    }
+    
+    pub fn fund_stake_bonus(ctx: Context<FundStakeBonus>, amount: u64) -> Result<()> {
+        fair_launch::fund_stake_bonus(ctx, amount)
+    }
+    
+    pub fn set_proceeds_recipients(
+        ctx: Context<SetProceedsRecipients>,
+        marketing_wallet: Pubkey,
+        marketing_share_bps: u16,
+        team_wallet: Pubkey,
+        team_share_bps: u16,
+        treasury_wallet: Pubkey,
+        treasury_share_bps: u16,
+    ) -> Result<()> {
+        fair_launch::set_proceeds_recipients(
+            ctx,
+            marketing_wallet,
+            marketing_share_bps,
+            team_wallet,
+            team_share_bps,
+            treasury_wallet,
+            treasury_share_bps,
+        )
+    }
+    
+    pub fn withdraw_launch_proceeds(ctx: Context<WithdrawLaunchProceeds>) -> Result<()> {
+        fair_launch::withdraw_launch_proceeds(ctx)
+    }
+    
+    pub fn withdraw_payment_proceeds(ctx: Context<WithdrawPaymentProceeds>) -> Result<()> {
+        fair_launch::withdraw_payment_proceeds(ctx)
+    }
+    
+    pub fn cancel_fair_launch(ctx: Context<CancelFairLaunch>) -> Result<()> {
+        fair_launch::cancel_fair_launch(ctx)
+    }
+    
+    pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        fair_launch::refund(ctx)
+    }
+    
+    pub fn refund_token(ctx: Context<RefundToken>) -> Result<()> {
+        fair_launch::refund_token(ctx)
+    }
+    
+    // Liquidity Pool Contract
+    pub fn initialize_amm_pool(ctx: Context<InitializeAmmPool>, swap_fee_bps: u16) -> Result<()> {
+        liquidity_pool::initialize_amm_pool(ctx, swap_fee_bps)
+    }
+    
+    pub fn swap(
+        ctx: Context<Swap>,
+        amount_in: u64,
+        minimum_amount_out: u64,
+        sol_to_token: bool,
+    ) -> Result<()> {
+        liquidity_pool::swap(ctx, amount_in, minimum_amount_out, sol_to_token)
+    }
+    
+    pub fn add_liquidity(
+        ctx: Context<AddLiquidity>,
+        sol_amount: u64,
+        max_token_amount: u64,
+        minimum_lp_out: u64,
     ) -> Result<()> {
-        fair_launch::contribute(ctx, amount, day, referrer)
+        liquidity_pool::add_liquidity(ctx, sol_amount, max_token_amount, minimum_lp_out)
     }
     
-    pub fn generate_daily_supply(ctx: Context<GenerateDailySupply>, day: u8) -> Result<()> {
-        fair_launch::generate_daily_supply(ctx, day)
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        lp_amount: u64,
+        minimum_sol_out: u64,
+        minimum_token_out: u64,
+    ) -> Result<()> {
+        liquidity_pool::remove_liquidity(ctx, lp_amount, minimum_sol_out, minimum_token_out)
     }
     
-    pub fn create_liquidity(ctx: Context<CreateLiquidity>) -> Result<()> {
-        fair_launch::create_liquidity(ctx)
+    // LP Lock Contract
+    pub fn extend_lp_lock(ctx: Context<ExtendLpLock>, new_unlock_timestamp: i64) -> Result<()> {
+        lp_lock::extend_lp_lock(ctx, new_unlock_timestamp)
     }
     
-    pub fn claim_tokens(ctx: Context<ClaimTokens>) -> Result<()> {
-        fair_launch::claim_tokens(ctx)
+    pub fn burn_locked_lp(ctx: Context<BurnLockedLp>) -> Result<()> {
+        lp_lock::burn_locked_lp(ctx)
     }
     
-    pub fn claim_referral_bonus(ctx: Context<ClaimReferralBonus>) -> Result<()> {
-        fair_launch::claim_referral_bonus(ctx)
+    pub fn release_lp(ctx: Context<ReleaseLp>) -> Result<()> {
+        lp_lock::release_lp(ctx)
     }
     
     // Staking Contract
@@ -519,34 +1138,186 @@ pub mod chad_empire {
     }
 }
 
+// Helper function to calculate a destination's share of the tax
+pub(crate) fn calculate_tax_share(tax_amount: u64, share_bps: u16) -> u64 {
+    ((tax_amount as u128) * (share_bps as u128) / 10000) as u64
+}
+
+// Helper function to split a tax between rewards, burn, auto-liquidity and treasury
+// The treasury receives the rounding remainder so no tax is lost
+// Every leg is sent without the mint's transfer fee, so each destination gets exactly its share
+// Taxes taken from a pool vault pass the pool PDA's signer seeds
+#[allow(clippy::too_many_arguments)]
+pub fn distribute_tax<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    rewards_pool: &AccountInfo<'info>,
+    liquidity_accumulator: &AccountInfo<'info>,
+    treasury: &AccountInfo<'info>,
+    fee_authority: &AccountInfo<'info>,
+    fee_authority_bump: u8,
+    token_config: &mut Account<'info, TokenConfig>,
+    tax_amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let rewards_amount = calculate_tax_share(tax_amount, token_config.rewards_tax_share_bps);
+    let burn_amount = calculate_tax_share(tax_amount, token_config.burn_tax_share_bps);
+    let liquidity_amount = calculate_tax_share(tax_amount, token_config.liquidity_tax_share_bps);
+    let treasury_amount = tax_amount
+        .checked_sub(rewards_amount)
+        .unwrap()
+        .checked_sub(burn_amount)
+        .unwrap()
+        .checked_sub(liquidity_amount)
+        .unwrap();
+    
+    // Transfer rewards share to rewards pool
+    if rewards_amount > 0 {
+        transfer_fee::transfer_checked_without_fee(
+            token_program,
+            from,
+            mint,
+            rewards_pool,
+            authority,
+            signer_seeds,
+            fee_authority,
+            fee_authority_bump,
+            rewards_amount,
+            decimals,
+        )?;
+    }
+    
+    // Burn the burn share
+    if burn_amount > 0 {
+        burn_and_record(
+            token_program,
+            mint,
+            from,
+            authority,
+            signer_seeds,
+            token_config,
+            burn_amount,
+            TokenConfig::BURN_SOURCE_TAX,
+        )?;
+    }
+    
+    // Transfer liquidity share to the auto-liquidity accumulator
+    if liquidity_amount > 0 {
+        transfer_fee::transfer_checked_without_fee(
+            token_program,
+            from,
+            mint,
+            liquidity_accumulator,
+            authority,
+            signer_seeds,
+            fee_authority,
+            fee_authority_bump,
+            liquidity_amount,
+            decimals,
+        )?;
+    }
+    
+    // Transfer treasury share to the team treasury
+    if treasury_amount > 0 {
+        transfer_fee::transfer_checked_without_fee(
+            token_program,
+            from,
+            mint,
+            treasury,
+            authority,
+            signer_seeds,
+            fee_authority,
+            fee_authority_bump,
+            treasury_amount,
+            decimals,
+        )?;
+    }
+    
+    // Update tax stats, the burn was recorded above
+    token_config.total_tax_to_rewards = token_config.total_tax_to_rewards.checked_add(rewards_amount).unwrap();
+    token_config.total_tax_to_liquidity = token_config.total_tax_to_liquidity.checked_add(liquidity_amount).unwrap();
+    token_config.total_tax_to_treasury = token_config.total_tax_to_treasury.checked_add(treasury_amount).unwrap();
+    
+    Ok(())
+}
+
+// Helper function to burn tokens and record the burn on the token config
+// Protocol vaults owned by a PDA pass its signer seeds, user accounts pass none
+#[allow(clippy::too_many_arguments)]
+pub fn burn_and_record<'info>(
+    token_program: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    token_config: &mut Account<'info, TokenConfig>,
+    amount: u64,
+    source: u8,
+) -> Result<()> {
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            token_interface::Burn {
+                mint: mint.clone(),
+                from: from.clone(),
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+    
+    token_config.record_burn(amount, source);
+    
+    Ok(())
+}
+
 #[derive(Accounts)]
 pub struct InitializeToken<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
+    /// Created as a Token-2022 mint with the transfer fee extension
+    #[account(mut)]
+    pub mint: Signer<'info>,
+    
+    /// Created as a Token-2022 account of the new mint
+    #[account(mut)]
+    pub authority_token_account: Signer<'info>,
+    
+    /// Created as a Token-2022 account of the new mint
+    #[account(mut)]
+    pub rewards_pool: Signer<'info>,
+    
+    /// CHECK: PDA allowed to withdraw withheld transfer fees from the mint
     #[account(
-        init,
-        payer = authority,
-        mint::decimals = 9,
-        mint::authority = authority,
+        seeds = [b"fee_authority".as_ref(), mint.key().as_ref()],
+        bump
     )]
-    pub mint: Account<'info, Mint>,
+    pub fee_authority: UncheckedAccount<'info>,
     
+    /// CHECK: Metaplex metadata account of the mint, created by the metadata program
     #[account(
-        init,
-        payer = authority,
-        token::mint = mint,
-        token::authority = authority,
+        mut,
+        seeds = [b"metadata".as_ref(), metadata_program.key().as_ref(), mint.key().as_ref()],
+        bump,
+        seeds::program = metadata_program.key()
     )]
-    pub authority_token_account: Account<'info, TokenAccount>,
+    pub metadata: UncheckedAccount<'info>,
     
+    /// CHECK: PDA set as the update authority of the metadata account
     #[account(
-        init,
-        payer = authority,
-        token::mint = mint,
-        token::authority = authority,
+        seeds = [b"metadata_authority".as_ref(), mint.key().as_ref()],
+        bump
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub metadata_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = token_metadata::metadata_program::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
     
     #[account(
         init,
@@ -556,7 +1327,7 @@ pub struct InitializeToken<'info> {
     pub token_config: Account<'info, TokenConfig>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Program<'info, Token2022>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -565,35 +1336,267 @@ pub struct LockMinting<'info> {
     pub authority: Signer<'info>,
     #[account(mut)]
     pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        mut,
+        address = token_config.mint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Program<'info, Token2022>,
 }
 
 #[derive(Accounts)]
 pub struct TransferWithTax<'info> {
     pub authority: Signer<'info>,
     
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+    )]
+    pub to_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = rewards_pool.key() == token_config.rewards_pool
+    )]
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = liquidity_accumulator.key() == token_config.liquidity_accumulator
+    )]
+    pub liquidity_accumulator: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        constraint = treasury.key() == token_config.treasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = token_config.mint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
     #[account(mut)]
-    pub from_token_account: Account<'info, TokenAccount>,
+    pub token_config: Account<'info, TokenConfig>,
+    
+    /// CHECK: PDA set as the withdraw withheld authority of the mint, releases the fee of program transfers
+    #[account(
+        seeds = [b"fee_authority".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_authority: UncheckedAccount<'info>,
+    
+    /// CHECK: Registry entry of the source account, only initialized if it is a pool
+    #[account(
+        seeds = [b"liquidity_pool".as_ref(), token_config.key().as_ref(), from_token_account.key().as_ref()],
+        bump
+    )]
+    pub from_pool_entry: UncheckedAccount<'info>,
+    
+    /// CHECK: Registry entry of the destination account, only initialized if it is a pool
+    #[account(
+        seeds = [b"liquidity_pool".as_ref(), token_config.key().as_ref(), to_token_account.key().as_ref()],
+        bump
+    )]
+    pub to_pool_entry: UncheckedAccount<'info>,
+    
+    // Exemption of the source token account or its owner, if any
+    #[account(
+        constraint = from_exemption.token_config == token_config.key(),
+        constraint = from_exemption.exempt_key == from_token_account.key()
+            || from_exemption.exempt_key == from_token_account.owner
+    )]
+    pub from_exemption: Option<Account<'info, TaxExemption>>,
+    
+    // Exemption of the destination token account or its owner, if any
+    #[account(
+        constraint = to_exemption.token_config == token_config.key(),
+        constraint = to_exemption.exempt_key == to_token_account.key()
+            || to_exemption.exempt_key == to_token_account.owner
+    )]
+    pub to_exemption: Option<Account<'info, TaxExemption>>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTaxRates<'info> {
+    pub authority: Signer<'info>,
     
     #[account(mut)]
-    pub to_token_account: Account<'info, TokenAccount>,
+    pub token_config: Account<'info, TokenConfig>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyTaxRates<'info> {
+    pub cranker: Signer<'info>,
     
     #[account(mut)]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        mut,
+        address = token_config.mint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    /// CHECK: PDA set as the transfer fee config authority of the mint
+    #[account(
+        seeds = [b"fee_authority".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTaxSplit<'info> {
+    pub authority: Signer<'info>,
     
+    #[account(mut)]
     pub token_config: Account<'info, TokenConfig>,
     
-    pub token_program: Program<'info, Token>,
+    #[account(
+        token::mint = token_config.mint,
+    )]
+    pub liquidity_accumulator: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        token::mint = token_config.mint,
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateTaxRates<'info> {
+pub struct BurnTokens<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+    )]
+    pub from_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        mut,
+        address = token_config.mint
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateTransferLimits<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateLaunchTax<'info> {
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidityPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        token::mint = token_config.mint,
+    )]
+    pub pool_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + LiquidityPoolEntry::LEN,
+        seeds = [b"liquidity_pool".as_ref(), token_config.key().as_ref(), pool_token_account.key().as_ref()],
+        bump
+    )]
+    pub pool_entry: Account<'info, LiquidityPoolEntry>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidityPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"liquidity_pool".as_ref(), token_config.key().as_ref(), pool_entry.pool_token_account.as_ref()],
+        bump
+    )]
+    pub pool_entry: Account<'info, LiquidityPoolEntry>,
+}
+
+#[derive(Accounts)]
+#[instruction(exempt_key: Pubkey)]
+pub struct AddTaxExemption<'info> {
+    #[account(mut)]
     pub authority: Signer<'info>,
     
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + TaxExemption::LEN,
+        seeds = [b"tax_exemption".as_ref(), token_config.key().as_ref(), exempt_key.as_ref()],
+        bump
+    )]
+    pub tax_exemption: Account<'info, TaxExemption>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveTaxExemption<'info> {
     #[account(mut)]
+    pub authority: Signer<'info>,
+    
     pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"tax_exemption".as_ref(), token_config.key().as_ref(), tax_exemption.exempt_key.as_ref()],
+        bump
+    )]
+    pub tax_exemption: Account<'info, TaxExemption>,
 }
 
 #[account]
+#[derive(Default)]
 pub struct TokenConfig {
     pub authority: Pubkey,
     pub mint: Pubkey,
@@ -603,10 +1606,162 @@ pub struct TokenConfig {
     pub minting_locked: bool,
     pub total_supply: u64,
     pub decimals: u8,
+    pub transfer_fee_bps: u16,     // Latest fee set on the mint, in force two epochs after it was set
+    pub total_fees_harvested: u64, // Withheld fees swept into the rewards pool
+    pub registered_pools: u16,     // Number of AMM pools in the registry
+    pub liquidity_accumulator: Pubkey, // Token account collecting tax for auto-liquidity
+    pub treasury: Pubkey,              // Team treasury token account
+    pub rewards_tax_share_bps: u16,    // Share of tax sent to the rewards pool in basis points
+    pub burn_tax_share_bps: u16,       // Share of tax burned in basis points
+    pub liquidity_tax_share_bps: u16,  // Share of tax sent to the liquidity accumulator in basis points
+    pub treasury_tax_share_bps: u16,   // Share of tax sent to the treasury in basis points
+    pub total_tax_to_rewards: u64,
+    pub total_tax_burned: u64,
+    pub total_tax_to_liquidity: u64,
+    pub total_tax_to_treasury: u64,
+    pub max_tx_amount: u64,            // Largest taxed transfer allowed, 0 = no limit
+    pub max_wallet_balance: u64,       // Largest balance a transfer may create, 0 = no limit
+    pub transfer_limits_lifted: bool,  // Once true, limits can never be set again
+    pub trading_enabled_at: i64,       // Set by create_liquidity, 0 = trading not open yet
+    pub launch_tax_bps: u16,           // Buy tax when trading opens, decays to buy_tax_bps
+    pub launch_tax_decay_seconds: i64, // Time for the launch tax to decay to buy_tax_bps
+    pub metadata_locked: bool,         // Once true, the token metadata can never be changed
+    pub circulating_supply: u64,       // Minted supply minus everything burned
+    pub total_user_burned: u64,
+    pub total_penalty_burned: u64,
+    pub total_buyback_burned: u64,
+    pub pending_buy_tax_bps: u16,      // Scheduled buy tax, applied by apply_tax_rates
+    pub pending_sell_tax_bps: u16,     // Scheduled sell tax, applied by apply_tax_rates
+    pub pending_tax_effective_at: i64, // When the scheduled rates can be applied, 0 = none
+    pub pending_transfer_fee_bps: u16, // Scheduled mint transfer fee, applied by apply_tax_rates
 }
 
 impl TokenConfig {
-    pub const LEN: usize = 32 + 32 + 32 + 2 + 2 + 1 + 8 + 1;
+    pub const LEN: usize = 32 + 32 + 32 + 2 + 2 + 1 + 8 + 1 + 2 + 8 + 2 + 32 + 32 + 2 + 2 + 2 + 2 + 8 + 8 + 8 + 8 + 8 + 8 + 1 + 8 + 2 + 8 + 1 + 8 + 8 + 8 + 8 + 2 + 2 + 8 + 2;
+    pub const MAX_REGISTERED_POOLS: u16 = 16;
+    pub const MAX_LAUNCH_TAX_BPS: u16 = 9000; // 90%
+    pub const DEFAULT_LAUNCH_TAX_BPS: u16 = 9000;
+    pub const DEFAULT_LAUNCH_TAX_DECAY_SECONDS: i64 = 10 * 60; // 10 minutes
+    pub const TAX_CHANGE_DELAY_SECONDS: i64 = 7 * 86400; // 1 week timelock on tax changes
+    pub const MAX_TAX_STEP_BPS: u16 = 200; // Max 2% change per update
+    
+    // Burn sources
+    pub const BURN_SOURCE_USER: u8 = 1;
+    pub const BURN_SOURCE_TAX: u8 = 2;
+    pub const BURN_SOURCE_PENALTY: u8 = 3;
+    pub const BURN_SOURCE_BUYBACK: u8 = 4;
+    
+    // Update the circulating supply and the burned total of the source
+    pub fn record_burn(&mut self, amount: u64, source: u8) {
+        self.circulating_supply = self.circulating_supply.checked_sub(amount).unwrap();
+        
+        let total_burned = match source {
+            TokenConfig::BURN_SOURCE_TAX => &mut self.total_tax_burned,
+            TokenConfig::BURN_SOURCE_PENALTY => &mut self.total_penalty_burned,
+            TokenConfig::BURN_SOURCE_BUYBACK => &mut self.total_buyback_burned,
+            _ => &mut self.total_user_burned,
+        };
+        *total_burned = total_burned.checked_add(amount).unwrap();
+    }
+    
+    // Check the trading gate and anti-whale limits of a transfer and return its tax rate
+    // Buys and sells are only possible once trading has been opened, regular transfers are not taxed
+    // Exempt accounts are neither gated, limited nor taxed
+    pub fn transfer_tax_bps(
+        &self,
+        amount: u64,
+        recipient_balance: u64,
+        is_buy: bool,
+        is_sell: bool,
+        is_exempt: bool,
+        current_time: i64,
+    ) -> Result<u16> {
+        if is_exempt {
+            return Ok(0);
+        }
+        
+        if is_buy || is_sell {
+            require!(
+                self.trading_enabled_at > 0 && current_time >= self.trading_enabled_at,
+                ErrorCode::TradingNotEnabled
+            );
+        }
+        
+        if !self.transfer_limits_lifted {
+            require!(
+                self.max_tx_amount == 0 || amount <= self.max_tx_amount,
+                ErrorCode::MaxTxAmountExceeded
+            );
+            
+            // Pools hold the market's liquidity and are not subject to the wallet limit
+            if !is_sell && self.max_wallet_balance > 0 {
+                let new_balance = recipient_balance.checked_add(amount).unwrap();
+                require!(
+                    new_balance <= self.max_wallet_balance,
+                    ErrorCode::MaxWalletBalanceExceeded
+                );
+            }
+        }
+        
+        if is_sell {
+            Ok(self.sell_tax_bps)
+        } else if is_buy {
+            Ok(self.current_buy_tax_bps(current_time))
+        } else {
+            Ok(0)
+        }
+    }
+    
+    // Buy tax at the given time, decaying linearly from the launch tax to buy_tax_bps
+    pub fn current_buy_tax_bps(&self, current_time: i64) -> u16 {
+        if self.trading_enabled_at == 0 || self.launch_tax_bps <= self.buy_tax_bps {
+            return self.buy_tax_bps;
+        }
+        
+        let elapsed = current_time.saturating_sub(self.trading_enabled_at);
+        if elapsed >= self.launch_tax_decay_seconds {
+            return self.buy_tax_bps;
+        }
+        
+        let excess_bps = (self.launch_tax_bps - self.buy_tax_bps) as i64;
+        let remaining_bps = excess_bps * (self.launch_tax_decay_seconds - elapsed) / self.launch_tax_decay_seconds;
+        self.buy_tax_bps + remaining_bps as u16
+    }
+}
+
+#[account]
+pub struct LiquidityPoolEntry {
+    pub token_config: Pubkey,
+    pub pool_token_account: Pubkey, // CHAD vault of the AMM pool
+    pub added_at: i64,
+}
+
+impl LiquidityPoolEntry {
+    pub const LEN: usize = 32 + 32 + 8;
+    
+    // An entry only exists (and is owned by this program) while the pool is registered
+    pub fn is_registered(entry: &AccountInfo) -> bool {
+        entry.owner == &crate::ID && !entry.data_is_empty()
+    }
+}
+
+#[account]
+pub struct TaxExemption {
+    pub token_config: Pubkey,
+    pub exempt_key: Pubkey, // Exempt token account, or owner whose accounts are all exempt
+    pub added_at: i64,
+}
+
+impl TaxExemption {
+    pub const LEN: usize = 32 + 32 + 8;
+}
+
+#[event]
+pub struct TaxExemptionUpdated {
+    pub token_config: Pubkey,
+    pub exempt_key: Pubkey,
+    pub exempt: bool, // false when the exemption was removed
+    pub timestamp: i64,
 }
 
 #[error_code]
@@ -615,4 +1770,119 @@ pub enum ErrorCode {
     Unauthorized,
     #[msg("Tax rate cannot exceed 20%")]
     TaxTooHigh,
+    #[msg("Liquidity pool registry is full")]
+    PoolRegistryFull,
+    #[msg("Tax split must add up to 100%")]
+    InvalidTaxSplit,
+    #[msg("Transfer amount exceeds the maximum transaction amount")]
+    MaxTxAmountExceeded,
+    #[msg("Transfer would exceed the maximum wallet balance")]
+    MaxWalletBalanceExceeded,
+    #[msg("Transfer limits have been permanently lifted")]
+    TransferLimitsLifted,
+    #[msg("Trading has not been enabled yet")]
+    TradingNotEnabled,
+    #[msg("Trading has already been enabled")]
+    TradingAlreadyEnabled,
+    #[msg("Launch tax decay cannot be negative")]
+    InvalidLaunchTaxDecay,
+    #[msg("Minted supply does not match the expected total supply")]
+    SupplyMismatch,
+    #[msg("Minting is already locked")]
+    MintingAlreadyLocked,
+    #[msg("Burn amount must be greater than zero")]
+    InvalidBurnAmount,
+    #[msg("Tax change exceeds the maximum step")]
+    TaxStepTooLarge,
+    #[msg("No tax change is pending")]
+    NoPendingTaxChange,
+    #[msg("Tax change is still timelocked")]
+    TaxChangeTimelocked,
+    #[msg("Transfer fee cannot exceed the lower tax rate")]
+    TransferFeeTooHigh,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+
+    fn open_token_config() -> TokenConfig {
+        TokenConfig {
+            buy_tax_bps: 1000,
+            sell_tax_bps: 800,
+            trading_enabled_at: NOW - 3600,
+            launch_tax_bps: 9000,
+            launch_tax_decay_seconds: 600,
+            ..Default::default()
+        }
+    }
+
+    fn assert_error<T: std::fmt::Debug>(result: Result<T>, error: ErrorCode) {
+        assert_eq!(result.unwrap_err(), error.into());
+    }
+
+    #[test]
+    fn buys_and_sells_are_gated_until_trading_opens() {
+        let mut token_config = open_token_config();
+        token_config.trading_enabled_at = 0;
+        assert_error(token_config.transfer_tax_bps(100, 0, true, false, false, NOW), ErrorCode::TradingNotEnabled);
+        assert_error(token_config.transfer_tax_bps(100, 0, false, true, false, NOW), ErrorCode::TradingNotEnabled);
+
+        token_config.trading_enabled_at = NOW + 1;
+        assert_error(token_config.transfer_tax_bps(100, 0, true, false, false, NOW), ErrorCode::TradingNotEnabled);
+
+        // Regular and exempt transfers are not gated
+        assert_eq!(token_config.transfer_tax_bps(100, 0, false, false, false, NOW).unwrap(), 0);
+        assert_eq!(token_config.transfer_tax_bps(100, 0, true, false, true, NOW).unwrap(), 0);
+    }
+
+    #[test]
+    fn buys_and_sells_pay_their_tax() {
+        let token_config = open_token_config();
+        assert_eq!(token_config.transfer_tax_bps(100, 0, true, false, false, NOW).unwrap(), 1000);
+        assert_eq!(token_config.transfer_tax_bps(100, 0, false, true, false, NOW).unwrap(), 800);
+        assert_eq!(token_config.transfer_tax_bps(100, 0, false, false, false, NOW).unwrap(), 0);
+        assert_eq!(token_config.transfer_tax_bps(100, 0, false, true, true, NOW).unwrap(), 0);
+    }
+
+    #[test]
+    fn launch_buy_tax_decays() {
+        let mut token_config = open_token_config();
+        token_config.trading_enabled_at = NOW;
+        assert_eq!(token_config.transfer_tax_bps(100, 0, true, false, false, NOW).unwrap(), 9000);
+        assert_eq!(token_config.transfer_tax_bps(100, 0, true, false, false, NOW + 300).unwrap(), 5000);
+        assert_eq!(token_config.transfer_tax_bps(100, 0, true, false, false, NOW + 600).unwrap(), 1000);
+        // The sell tax does not decay
+        assert_eq!(token_config.transfer_tax_bps(100, 0, false, true, false, NOW).unwrap(), 800);
+    }
+
+    #[test]
+    fn limits_apply_until_lifted() {
+        let mut token_config = open_token_config();
+        token_config.max_tx_amount = 1_000;
+        token_config.max_wallet_balance = 5_000;
+
+        assert_error(token_config.transfer_tax_bps(1_001, 0, true, false, false, NOW), ErrorCode::MaxTxAmountExceeded);
+        assert_error(
+            token_config.transfer_tax_bps(1_000, 4_001, true, false, false, NOW),
+            ErrorCode::MaxWalletBalanceExceeded,
+        );
+        assert!(token_config.transfer_tax_bps(1_000, 4_000, true, false, false, NOW).is_ok());
+        // Pools are not subject to the wallet limit
+        assert!(token_config.transfer_tax_bps(1_000, 1_000_000, false, true, false, NOW).is_ok());
+        // Exempt accounts are not limited
+        assert!(token_config.transfer_tax_bps(1_000_000, 1_000_000, true, false, true, NOW).is_ok());
+
+        token_config.transfer_limits_lifted = true;
+        assert!(token_config.transfer_tax_bps(1_000_000, 1_000_000, true, false, false, NOW).is_ok());
+    }
+
+    #[test]
+    fn tax_split_rounds_down() {
+        assert_eq!(calculate_tax_share(1_000, 2500), 250);
+        assert_eq!(calculate_tax_share(999, 3333), 332);
+        assert_eq!(calculate_tax_share(u64::MAX, 10000), u64::MAX);
+    }
 }
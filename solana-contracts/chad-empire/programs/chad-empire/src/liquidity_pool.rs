@@ -0,0 +1,786 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+use solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
+
+use crate::{TaxExemption, TokenConfig};
+
+#[account]
+pub struct AmmPool {
+    pub token_mint: Pubkey,
+    pub token_vault: Pubkey,  // CHAD reserve, owned by the pool PDA
+    pub sol_vault: Pubkey,    // SOL reserve, a system account PDA
+    pub lp_mint: Pubkey,      // LP token, minted and burned by the pool PDA
+    pub token_reserve: u64,
+    pub sol_reserve: u64,
+    pub swap_fee_bps: u16,    // Fee kept in the pool on every swap, in basis points
+    pub seeded: bool,         // Set once create_liquidity has provided the initial liquidity
+}
+
+impl AmmPool {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 2 + 1;
+    pub const MAX_SWAP_FEE_BPS: u16 = 100; // Max 1% swap fee
+}
+
+pub fn initialize_amm_pool(ctx: Context<InitializeAmmPool>, swap_fee_bps: u16) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+        ErrorCode::Unauthorized
+    );
+    require!(swap_fee_bps <= AmmPool::MAX_SWAP_FEE_BPS, ErrorCode::SwapFeeTooHigh);
+
+    let amm_pool = &mut ctx.accounts.amm_pool;
+    amm_pool.token_mint = ctx.accounts.token_mint.key();
+    amm_pool.token_vault = ctx.accounts.pool_token_vault.key();
+    amm_pool.sol_vault = ctx.accounts.pool_sol_vault.key();
+    amm_pool.lp_mint = ctx.accounts.lp_mint.key();
+    amm_pool.token_reserve = 0;
+    amm_pool.sol_reserve = 0;
+    amm_pool.swap_fee_bps = swap_fee_bps;
+    amm_pool.seeded = false;
+
+    Ok(())
+}
+
+pub fn swap(
+    ctx: Context<Swap>,
+    amount_in: u64,
+    minimum_amount_out: u64,
+    sol_to_token: bool,
+) -> Result<()> {
+    require!(amount_in > 0, ErrorCode::InvalidAmount);
+
+    let amm_pool = &ctx.accounts.amm_pool;
+    require!(amm_pool.seeded, ErrorCode::PoolNotSeeded);
+
+    // Swaps are buys and sells, so they are gated, limited and taxed like transfer_with_tax
+    let is_exempt = ctx.accounts.user_exemption.is_some();
+    let current_time = Clock::get()?.unix_timestamp;
+    let fee_authority_bump = *ctx.bumps.get("fee_authority").unwrap();
+
+    let token_mint_key = amm_pool.token_mint;
+    let pool_seeds: &[&[u8]] = &[
+        b"amm_pool".as_ref(),
+        token_mint_key.as_ref(),
+        &[*ctx.bumps.get("amm_pool").unwrap()],
+    ];
+
+    if sol_to_token {
+        // Buy: SOL in, CHAD out, the buy tax is taken from the output
+        let amount_out = calculate_swap_output(
+            amount_in,
+            amm_pool.sol_reserve,
+            amm_pool.token_reserve,
+            amm_pool.swap_fee_bps,
+        );
+        require!(amount_out > 0, ErrorCode::InvalidAmount);
+
+        let tax_bps = ctx.accounts.token_config.transfer_tax_bps(
+            amount_out,
+            ctx.accounts.user_token_account.amount,
+            true,
+            false,
+            is_exempt,
+            current_time,
+        )?;
+        let tax_amount = crate::calculate_tax_share(amount_out, tax_bps);
+        let received_amount = amount_out.checked_sub(tax_amount).unwrap();
+        require!(received_amount >= minimum_amount_out, ErrorCode::SlippageExceeded);
+
+        invoke(
+            &system_instruction::transfer(
+                ctx.accounts.user.key,
+                ctx.accounts.pool_sol_vault.key,
+                amount_in,
+            ),
+            &[
+                ctx.accounts.user.to_account_info(),
+                ctx.accounts.pool_sol_vault.to_account_info(),
+                ctx.accounts.system_program.to_account_info(),
+            ],
+        )?;
+
+        // Split the tax out of the pool vault, signed by the pool PDA
+        crate::distribute_tax(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.pool_token_vault.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.amm_pool.to_account_info(),
+            &[pool_seeds],
+            &ctx.accounts.rewards_pool.to_account_info(),
+            &ctx.accounts.liquidity_accumulator.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.fee_authority.to_account_info(),
+            fee_authority_bump,
+            &mut ctx.accounts.token_config,
+            tax_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        // The tax is the whole charge, so the mint's transfer fee is not added on top
+        crate::transfer_fee::transfer_checked_without_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.pool_token_vault.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.amm_pool.to_account_info(),
+            &[pool_seeds],
+            &ctx.accounts.fee_authority.to_account_info(),
+            fee_authority_bump,
+            received_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        let amm_pool = &mut ctx.accounts.amm_pool;
+        amm_pool.sol_reserve = amm_pool.sol_reserve.checked_add(amount_in).unwrap();
+        amm_pool.token_reserve = amm_pool.token_reserve.checked_sub(amount_out).unwrap();
+    } else {
+        // Sell: CHAD in, SOL out, the sell tax is taken from the input
+        let tax_bps = ctx.accounts.token_config.transfer_tax_bps(
+            amount_in,
+            ctx.accounts.pool_token_vault.amount,
+            false,
+            true,
+            is_exempt,
+            current_time,
+        )?;
+        let tax_amount = crate::calculate_tax_share(amount_in, tax_bps);
+        let swapped_amount = amount_in.checked_sub(tax_amount).unwrap();
+
+        let amount_out = calculate_swap_output(
+            swapped_amount,
+            amm_pool.token_reserve,
+            amm_pool.sol_reserve,
+            amm_pool.swap_fee_bps,
+        );
+        require!(amount_out >= minimum_amount_out, ErrorCode::SlippageExceeded);
+        require!(amount_out > 0, ErrorCode::InvalidAmount);
+
+        crate::distribute_tax(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &[],
+            &ctx.accounts.rewards_pool.to_account_info(),
+            &ctx.accounts.liquidity_accumulator.to_account_info(),
+            &ctx.accounts.treasury.to_account_info(),
+            &ctx.accounts.fee_authority.to_account_info(),
+            fee_authority_bump,
+            &mut ctx.accounts.token_config,
+            tax_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        // The whole amount after tax reaches the vault, so the reserve stays in sync with it
+        crate::transfer_fee::transfer_checked_without_fee(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.user_token_account.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.pool_token_vault.to_account_info(),
+            &ctx.accounts.user.to_account_info(),
+            &[],
+            &ctx.accounts.fee_authority.to_account_info(),
+            fee_authority_bump,
+            swapped_amount,
+            ctx.accounts.token_mint.decimals,
+        )?;
+
+        transfer_sol_from_pool(
+            &ctx.accounts.pool_sol_vault,
+            &ctx.accounts.user.to_account_info(),
+            &ctx.accounts.system_program,
+            &ctx.accounts.amm_pool.key(),
+            *ctx.bumps.get("pool_sol_vault").unwrap(),
+            amount_out,
+        )?;
+
+        let amm_pool = &mut ctx.accounts.amm_pool;
+        amm_pool.token_reserve = amm_pool.token_reserve.checked_add(swapped_amount).unwrap();
+        amm_pool.sol_reserve = amm_pool.sol_reserve.checked_sub(amount_out).unwrap();
+    }
+
+    Ok(())
+}
+
+pub fn add_liquidity(
+    ctx: Context<AddLiquidity>,
+    sol_amount: u64,
+    max_token_amount: u64,
+    minimum_lp_out: u64,
+) -> Result<()> {
+    require!(sol_amount > 0, ErrorCode::InvalidAmount);
+
+    let amm_pool = &ctx.accounts.amm_pool;
+    require!(amm_pool.seeded, ErrorCode::PoolNotSeeded);
+    require!(
+        amm_pool.sol_reserve > 0 && amm_pool.token_reserve > 0,
+        ErrorCode::PoolEmpty
+    );
+
+    // Deposit tokens in the current pool ratio, rounded up in favour of the pool
+    let token_amount = calculate_deposit_tokens(sol_amount, amm_pool.sol_reserve, amm_pool.token_reserve);
+    require!(token_amount <= max_token_amount, ErrorCode::SlippageExceeded);
+
+    invoke(
+        &system_instruction::transfer(
+            ctx.accounts.user.key,
+            ctx.accounts.pool_sol_vault.key,
+            sol_amount,
+        ),
+        &[
+            ctx.accounts.user.to_account_info(),
+            ctx.accounts.pool_sol_vault.to_account_info(),
+            ctx.accounts.system_program.to_account_info(),
+        ],
+    )?;
+
+    token_interface::transfer_checked(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.pool_token_vault.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        token_amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let transfer_fee = crate::transfer_fee::calculate_transfer_fee(
+        &ctx.accounts.token_mint.to_account_info(),
+        token_amount,
+    )?;
+    let received_amount = token_amount.checked_sub(transfer_fee).unwrap();
+
+    // LP tokens are minted for the smaller of the two contributions
+    let lp_amount = calculate_deposit_lp(
+        sol_amount,
+        received_amount,
+        amm_pool.sol_reserve,
+        amm_pool.token_reserve,
+        ctx.accounts.lp_mint.supply,
+    );
+    require!(lp_amount > 0 && lp_amount >= minimum_lp_out, ErrorCode::SlippageExceeded);
+
+    token_interface::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_account.to_account_info(),
+                authority: ctx.accounts.amm_pool.to_account_info(),
+            },
+            &[&[
+                b"amm_pool".as_ref(),
+                amm_pool.token_mint.as_ref(),
+                &[*ctx.bumps.get("amm_pool").unwrap()],
+            ]],
+        ),
+        lp_amount,
+    )?;
+
+    let amm_pool = &mut ctx.accounts.amm_pool;
+    amm_pool.sol_reserve = amm_pool.sol_reserve.checked_add(sol_amount).unwrap();
+    amm_pool.token_reserve = amm_pool.token_reserve.checked_add(received_amount).unwrap();
+
+    Ok(())
+}
+
+pub fn remove_liquidity(
+    ctx: Context<RemoveLiquidity>,
+    lp_amount: u64,
+    minimum_sol_out: u64,
+    minimum_token_out: u64,
+) -> Result<()> {
+    require!(lp_amount > 0, ErrorCode::InvalidAmount);
+
+    let amm_pool = &ctx.accounts.amm_pool;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+
+    // Withdraw the LP share of both reserves
+    let (sol_out, token_out) = calculate_withdrawal(
+        lp_amount,
+        lp_supply,
+        amm_pool.sol_reserve,
+        amm_pool.token_reserve,
+    );
+    require!(sol_out >= minimum_sol_out, ErrorCode::SlippageExceeded);
+    require!(token_out >= minimum_token_out, ErrorCode::SlippageExceeded);
+
+    token_interface::burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_account.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    transfer_sol_from_pool(
+        &ctx.accounts.pool_sol_vault,
+        &ctx.accounts.user.to_account_info(),
+        &ctx.accounts.system_program,
+        &amm_pool.key(),
+        *ctx.bumps.get("pool_sol_vault").unwrap(),
+        sol_out,
+    )?;
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.pool_token_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: ctx.accounts.amm_pool.to_account_info(),
+            },
+            &[&[
+                b"amm_pool".as_ref(),
+                amm_pool.token_mint.as_ref(),
+                &[*ctx.bumps.get("amm_pool").unwrap()],
+            ]],
+        ),
+        token_out,
+        ctx.accounts.token_mint.decimals,
+    )?;
+
+    let amm_pool = &mut ctx.accounts.amm_pool;
+    amm_pool.sol_reserve = amm_pool.sol_reserve.checked_sub(sol_out).unwrap();
+    amm_pool.token_reserve = amm_pool.token_reserve.checked_sub(token_out).unwrap();
+
+    Ok(())
+}
+
+// Helper function to calculate the output of a constant-product swap after the swap fee
+pub fn calculate_swap_output(amount_in: u64, reserve_in: u64, reserve_out: u64, swap_fee_bps: u16) -> u64 {
+    let amount_in_after_fee = (amount_in as u128) * (10000 - swap_fee_bps as u128) / 10000;
+    let numerator = amount_in_after_fee * (reserve_out as u128);
+    let denominator = (reserve_in as u128) + amount_in_after_fee;
+    if denominator == 0 {
+        return 0;
+    }
+
+    (numerator / denominator) as u64
+}
+
+// Helper function to calculate the tokens deposited with sol_amount, in the pool ratio
+// Rounds up, so a deposit never dilutes the pool
+pub fn calculate_deposit_tokens(sol_amount: u64, sol_reserve: u64, token_reserve: u64) -> u64 {
+    ((sol_amount as u128) * (token_reserve as u128))
+        .checked_add(sol_reserve as u128 - 1)
+        .unwrap()
+        .checked_div(sol_reserve as u128)
+        .unwrap() as u64
+}
+
+// Helper function to calculate the LP minted for a deposit, for the smaller of the two contributions
+// Rounds down, so the LP supply never grows faster than the reserves
+pub fn calculate_deposit_lp(
+    sol_amount: u64,
+    token_amount: u64,
+    sol_reserve: u64,
+    token_reserve: u64,
+    lp_supply: u64,
+) -> u64 {
+    let lp_for_sol = (sol_amount as u128) * (lp_supply as u128) / (sol_reserve as u128);
+    let lp_for_tokens = (token_amount as u128) * (lp_supply as u128) / (token_reserve as u128);
+    std::cmp::min(lp_for_sol, lp_for_tokens) as u64
+}
+
+// Helper function to calculate the SOL and tokens withdrawn for lp_amount
+// Rounds down, so withdrawals never take more than the LP share of the reserves
+pub fn calculate_withdrawal(lp_amount: u64, lp_supply: u64, sol_reserve: u64, token_reserve: u64) -> (u64, u64) {
+    let sol_out = ((lp_amount as u128) * (sol_reserve as u128) / (lp_supply as u128)) as u64;
+    let token_out = ((lp_amount as u128) * (token_reserve as u128) / (lp_supply as u128)) as u64;
+    (sol_out, token_out)
+}
+
+// Helper function to calculate the LP minted for the initial liquidity, sqrt(sol * tokens)
+pub fn calculate_initial_lp(sol_amount: u64, token_amount: u64) -> u64 {
+    let product = (sol_amount as u128) * (token_amount as u128);
+
+    // Integer square root by Newton's method
+    if product == 0 {
+        return 0;
+    }
+    let mut x = product;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + product / x) / 2;
+    }
+
+    x as u64
+}
+
+// Helper function to pay SOL out of the pool's SOL vault PDA
+pub fn transfer_sol_from_pool<'info>(
+    pool_sol_vault: &UncheckedAccount<'info>,
+    to: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+    amm_pool: &Pubkey,
+    pool_sol_vault_bump: u8,
+    amount: u64,
+) -> Result<()> {
+    invoke_signed(
+        &system_instruction::transfer(pool_sol_vault.key, to.key, amount),
+        &[
+            pool_sol_vault.to_account_info(),
+            to.clone(),
+            system_program.to_account_info(),
+        ],
+        &[&[
+            b"amm_sol_vault".as_ref(),
+            amm_pool.as_ref(),
+            &[pool_sol_vault_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeAmmPool<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = token_config.mint == token_mint.key())]
+    pub token_config: Account<'info, TokenConfig>,
+
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + AmmPool::LEN,
+        seeds = [b"amm_pool".as_ref(), token_mint.key().as_ref()],
+        bump
+    )]
+    pub amm_pool: Account<'info, AmmPool>,
+
+    #[account(
+        init,
+        payer = authority,
+        token::mint = token_mint,
+        token::authority = amm_pool,
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that will hold the pool's SOL
+    #[account(
+        seeds = [b"amm_sol_vault".as_ref(), amm_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_sol_vault: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = authority,
+        mint::decimals = token_mint.decimals,
+        mint::authority = amm_pool,
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_pool".as_ref(), amm_pool.token_mint.as_ref()],
+        bump
+    )]
+    pub amm_pool: Account<'info, AmmPool>,
+
+    #[account(
+        mut,
+        address = amm_pool.token_vault
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that holds the pool's SOL
+    #[account(
+        mut,
+        seeds = [b"amm_sol_vault".as_ref(), amm_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_sol_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = user,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Holds the tax rates, limits and trading gate applied to swaps
+    #[account(
+        mut,
+        constraint = token_config.mint == amm_pool.token_mint
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(
+        mut,
+        constraint = rewards_pool.key() == token_config.rewards_pool
+    )]
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = liquidity_accumulator.key() == token_config.liquidity_accumulator
+    )]
+    pub liquidity_accumulator: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = treasury.key() == token_config.treasury
+    )]
+    pub treasury: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: PDA set as the withdraw withheld authority of the mint, releases the fee of swap transfers
+    #[account(
+        seeds = [b"fee_authority".as_ref(), amm_pool.token_mint.as_ref()],
+        bump
+    )]
+    pub fee_authority: UncheckedAccount<'info>,
+
+    // Exemption of the user's token account or the user, if any
+    #[account(
+        constraint = user_exemption.token_config == token_config.key(),
+        constraint = user_exemption.exempt_key == user_token_account.key()
+            || user_exemption.exempt_key == user.key()
+    )]
+    pub user_exemption: Option<Account<'info, TaxExemption>>,
+
+    #[account(
+        mut,
+        address = amm_pool.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_pool".as_ref(), amm_pool.token_mint.as_ref()],
+        bump
+    )]
+    pub amm_pool: Account<'info, AmmPool>,
+
+    #[account(
+        mut,
+        address = amm_pool.token_vault
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that holds the pool's SOL
+    #[account(
+        mut,
+        seeds = [b"amm_sol_vault".as_ref(), amm_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_sol_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = amm_pool.lp_mint
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = user,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+    )]
+    pub user_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = amm_pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"amm_pool".as_ref(), amm_pool.token_mint.as_ref()],
+        bump
+    )]
+    pub amm_pool: Account<'info, AmmPool>,
+
+    #[account(
+        mut,
+        address = amm_pool.token_vault
+    )]
+    pub pool_token_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: This is a PDA that holds the pool's SOL
+    #[account(
+        mut,
+        seeds = [b"amm_sol_vault".as_ref(), amm_pool.key().as_ref()],
+        bump
+    )]
+    pub pool_sol_vault: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        address = amm_pool.lp_mint
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = token_mint,
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = user,
+    )]
+    pub user_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = amm_pool.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("You are not authorized to perform this action")]
+    Unauthorized,
+    #[msg("Swap fee too high")]
+    SwapFeeTooHigh,
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Pool has not been seeded with liquidity")]
+    PoolNotSeeded,
+    #[msg("Pool has no liquidity")]
+    PoolEmpty,
+    #[msg("Slippage tolerance exceeded")]
+    SlippageExceeded,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swap_output_follows_constant_product() {
+        // 1 SOL into a 100 SOL / 1,000,000 token pool without fee
+        assert_eq!(calculate_swap_output(1, 100, 1_000_000, 0), 9_900);
+        // The product of the reserves never decreases
+        let out = calculate_swap_output(1_000, 50_000, 80_000, 30);
+        assert!((50_000u128 + 1_000) * (80_000u128 - out as u128) >= 50_000u128 * 80_000);
+    }
+
+    #[test]
+    fn swap_fee_reduces_output() {
+        let without_fee = calculate_swap_output(1_000_000, 100_000_000, 100_000_000, 0);
+        let with_fee = calculate_swap_output(1_000_000, 100_000_000, 100_000_000, 100);
+        assert_eq!(without_fee, 990_099);
+        assert_eq!(with_fee, 980_295);
+    }
+
+    #[test]
+    fn swap_output_of_empty_pool_is_zero() {
+        assert_eq!(calculate_swap_output(0, 0, 1_000, 0), 0);
+        assert_eq!(calculate_swap_output(1_000, 0, 0, 0), 0);
+        // A dust input rounds down to nothing instead of draining the pool
+        assert_eq!(calculate_swap_output(1, 1_000_000, 1_000, 0), 0);
+    }
+
+    #[test]
+    fn initial_lp_is_integer_sqrt() {
+        assert_eq!(calculate_initial_lp(0, 1_000), 0);
+        assert_eq!(calculate_initial_lp(1, 1), 1);
+        assert_eq!(calculate_initial_lp(4, 9), 6);
+        assert_eq!(calculate_initial_lp(2, 5), 3); // sqrt(10) rounds down
+        assert_eq!(calculate_initial_lp(u64::MAX, u64::MAX), u64::MAX);
+
+        let lp = calculate_initial_lp(123_456_789, 987_654_321_000) as u128;
+        let product = 123_456_789u128 * 987_654_321_000;
+        assert!(lp * lp <= product && (lp + 1) * (lp + 1) > product);
+    }
+
+    #[test]
+    fn deposit_tokens_round_up() {
+        assert_eq!(calculate_deposit_tokens(10, 100, 1_000), 100);
+        // 1 * 1000 / 3 = 333.3, the depositor pays 334
+        assert_eq!(calculate_deposit_tokens(1, 3, 1_000), 334);
+        assert_eq!(calculate_deposit_tokens(1, 1_000, 1), 1);
+    }
+
+    #[test]
+    fn deposit_lp_rounds_down_to_smaller_contribution() {
+        assert_eq!(calculate_deposit_lp(10, 100, 100, 1_000, 500), 50);
+        // The token side is short, so LP follows the tokens
+        assert_eq!(calculate_deposit_lp(10, 90, 100, 1_000, 500), 45);
+        // 1 * 10 / 3 = 3.3 LP, rounded down
+        assert_eq!(calculate_deposit_lp(1, 334, 3, 1_000, 10), 3);
+    }
+
+    #[test]
+    fn withdrawal_rounds_down() {
+        assert_eq!(calculate_withdrawal(50, 500, 100, 1_000), (10, 100));
+        assert_eq!(calculate_withdrawal(1, 3, 100, 1_000), (33, 333));
+        assert_eq!(calculate_withdrawal(3, 3, 100, 1_000), (100, 1_000));
+    }
+
+    #[test]
+    fn add_then_remove_never_extracts_value() {
+        let (mut sol_reserve, mut token_reserve) = (1_000_003u64, 7_777_777u64);
+        let mut lp_supply = calculate_initial_lp(sol_reserve, token_reserve);
+
+        for sol_amount in [1u64, 7, 999, 123_457] {
+            let token_amount = calculate_deposit_tokens(sol_amount, sol_reserve, token_reserve);
+            let lp_amount = calculate_deposit_lp(sol_amount, token_amount, sol_reserve, token_reserve, lp_supply);
+            sol_reserve += sol_amount;
+            token_reserve += token_amount;
+            lp_supply += lp_amount;
+
+            let (sol_out, token_out) = calculate_withdrawal(lp_amount, lp_supply, sol_reserve, token_reserve);
+            assert!(sol_out <= sol_amount);
+            assert!(token_out <= token_amount);
+            sol_reserve -= sol_out;
+            token_reserve -= token_out;
+            lp_supply -= lp_amount;
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
+
+use crate::Proposal;
+
+#[account]
+pub struct LpLock {
+    pub authority: Pubkey,         // Can extend, burn or release the lock
+    pub lp_mint: Pubkey,
+    pub fair_launch_config: Pubkey, // Launch round whose liquidity is locked
+    pub lp_vault: Pubkey,          // LP token account owned by the lock PDA
+    pub locked_amount: u64,
+    pub locked_at: i64,
+    pub unlock_timestamp: i64,
+    pub governance_config: Pubkey, // If set, release needs an executed proposal of this governance
+    pub burned: bool,
+    pub released: bool,
+}
+
+impl LpLock {
+    pub const LEN: usize = 32 + 32 + 32 + 32 + 8 + 8 + 8 + 32 + 1 + 1;
+    pub const MIN_LOCK_SECONDS: i64 = 30 * 86400; // 30 days
+}
+
+pub fn extend_lp_lock(ctx: Context<ExtendLpLock>, new_unlock_timestamp: i64) -> Result<()> {
+    let lp_lock = &mut ctx.accounts.lp_lock;
+    require!(!lp_lock.burned && !lp_lock.released, ErrorCode::LockClosed);
+
+    // A lock can only ever be extended
+    require!(
+        new_unlock_timestamp > lp_lock.unlock_timestamp,
+        ErrorCode::UnlockTimestampNotLater
+    );
+
+    lp_lock.unlock_timestamp = new_unlock_timestamp;
+
+    Ok(())
+}
+
+pub fn burn_locked_lp(ctx: Context<BurnLockedLp>) -> Result<()> {
+    let lp_lock = &ctx.accounts.lp_lock;
+    require!(!lp_lock.burned && !lp_lock.released, ErrorCode::LockClosed);
+
+    // Burning the LP tokens makes the liquidity permanent
+    let lp_mint_key = lp_lock.lp_mint;
+    let fair_launch_config_key = lp_lock.fair_launch_config;
+    token_interface::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token_interface::Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.lp_vault.to_account_info(),
+                authority: ctx.accounts.lp_lock.to_account_info(),
+            },
+            &[&[
+                b"lp_lock".as_ref(),
+                lp_mint_key.as_ref(),
+                fair_launch_config_key.as_ref(),
+                &[*ctx.bumps.get("lp_lock").unwrap()],
+            ]],
+        ),
+        ctx.accounts.lp_vault.amount,
+    )?;
+
+    let lp_lock = &mut ctx.accounts.lp_lock;
+    lp_lock.burned = true;
+
+    Ok(())
+}
+
+pub fn release_lp(ctx: Context<ReleaseLp>) -> Result<()> {
+    let lp_lock = &ctx.accounts.lp_lock;
+    require!(!lp_lock.burned && !lp_lock.released, ErrorCode::LockClosed);
+
+    let current_time = Clock::get()?.unix_timestamp;
+    require!(current_time >= lp_lock.unlock_timestamp, ErrorCode::LockNotExpired);
+
+    // Governance-controlled locks need an executed proposal targeting this lock
+    if lp_lock.governance_config != Pubkey::default() {
+        let proposal = ctx
+            .accounts
+            .proposal
+            .as_ref()
+            .ok_or(ErrorCode::ReleaseProposalRequired)?;
+        let (expected_proposal, _) = Pubkey::find_program_address(
+            &[
+                b"proposal".as_ref(),
+                lp_lock.governance_config.as_ref(),
+                &proposal.proposal_id.to_le_bytes(),
+            ],
+            ctx.program_id,
+        );
+        require_keys_eq!(proposal.key(), expected_proposal, ErrorCode::ReleaseProposalMismatch);
+        require!(proposal.status == 5, ErrorCode::ReleaseProposalNotExecuted); // Executed
+        require!(
+            proposal.target_program == crate::ID
+                && proposal.instruction_data == lp_lock.key().to_bytes().to_vec(),
+            ErrorCode::ReleaseProposalMismatch
+        );
+    }
+
+    let lp_mint_key = lp_lock.lp_mint;
+    let fair_launch_config_key = lp_lock.fair_launch_config;
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: ctx.accounts.lp_vault.to_account_info(),
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.recipient_lp_account.to_account_info(),
+                authority: ctx.accounts.lp_lock.to_account_info(),
+            },
+            &[&[
+                b"lp_lock".as_ref(),
+                lp_mint_key.as_ref(),
+                fair_launch_config_key.as_ref(),
+                &[*ctx.bumps.get("lp_lock").unwrap()],
+            ]],
+        ),
+        ctx.accounts.lp_vault.amount,
+        ctx.accounts.lp_mint.decimals,
+    )?;
+
+    let lp_lock = &mut ctx.accounts.lp_lock;
+    lp_lock.released = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendLpLock<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock".as_ref(), lp_lock.lp_mint.as_ref(), lp_lock.fair_launch_config.as_ref()],
+        bump,
+        constraint = authority.key() == lp_lock.authority
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+}
+
+#[derive(Accounts)]
+pub struct BurnLockedLp<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock".as_ref(), lp_lock.lp_mint.as_ref(), lp_lock.fair_launch_config.as_ref()],
+        bump,
+        constraint = authority.key() == lp_lock.authority
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+
+    #[account(
+        mut,
+        address = lp_lock.lp_vault
+    )]
+    pub lp_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = lp_lock.lp_mint
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseLp<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lp_lock".as_ref(), lp_lock.lp_mint.as_ref(), lp_lock.fair_launch_config.as_ref()],
+        bump,
+        constraint = authority.key() == lp_lock.authority
+    )]
+    pub lp_lock: Account<'info, LpLock>,
+
+    #[account(
+        mut,
+        address = lp_lock.lp_vault
+    )]
+    pub lp_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(address = lp_lock.lp_mint)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+    )]
+    pub recipient_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    // Executed proposal approving the release, only needed for governance-controlled locks
+    pub proposal: Option<Account<'info, Proposal>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("LP lock has already been burned or released")]
+    LockClosed,
+    #[msg("New unlock timestamp must be later than the current one")]
+    UnlockTimestampNotLater,
+    #[msg("LP lock has not expired yet")]
+    LockNotExpired,
+    #[msg("Release requires a governance proposal")]
+    ReleaseProposalRequired,
+    #[msg("Release proposal has not been executed")]
+    ReleaseProposalNotExecuted,
+    #[msg("Proposal does not release this lock")]
+    ReleaseProposalMismatch,
+}
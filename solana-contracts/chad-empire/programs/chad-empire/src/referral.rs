@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use solana_program::{program::invoke, system_instruction};
 use std::convert::TryInto;
 
@@ -180,11 +180,12 @@ pub fn record_contribution(
         let token_commission = commission_amount.checked_mul(1000).unwrap();
         
         // Transfer tokens from rewards pool to referrer
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.rewards_pool.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.referrer_token_account.to_account_info(),
                     authority: ctx.accounts.referral_config.to_account_info(),
                 },
@@ -195,6 +196,7 @@ pub fn record_contribution(
                 ]],
             ),
             token_commission,
+            ctx.accounts.token_mint.decimals,
         )?;
         
         // Update referrer's commission earned
@@ -275,7 +277,7 @@ pub struct InitializeReferralSystem<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
@@ -290,10 +292,10 @@ pub struct InitializeReferralSystem<'info> {
         mut,
         token::mint = token_mint,
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -362,16 +364,19 @@ pub struct RecordContribution<'info> {
         token::mint = referral_config.token_mint,
         constraint = rewards_pool.key() == referral_config.rewards_pool
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = referral_config.token_mint,
         token::authority = referred_user_info.referrer,
     )]
-    pub referrer_token_account: Account<'info, TokenAccount>,
+    pub referrer_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_program: Program<'info, Token>,
+    #[account(address = referral_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
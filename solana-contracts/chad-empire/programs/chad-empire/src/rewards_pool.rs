@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use solana_program::{program::invoke, system_instruction};
 use std::convert::TryInto;
 
@@ -136,11 +136,12 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
     
     // Transfer to staking sub-pool
     if staking_amount > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.rewards_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.staking_token_account.to_account_info(),
                     authority: ctx.accounts.rewards_pool_config.to_account_info(),
                 },
@@ -151,6 +152,7 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
                 ]],
             ),
             staking_amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         
         // Update staking sub-pool stats
@@ -160,11 +162,12 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
     
     // Transfer to spin sub-pool
     if spin_amount > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.rewards_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.spin_token_account.to_account_info(),
                     authority: ctx.accounts.rewards_pool_config.to_account_info(),
                 },
@@ -175,6 +178,7 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
                 ]],
             ),
             spin_amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         
         // Update spin sub-pool stats
@@ -184,11 +188,12 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
     
     // Transfer to referral sub-pool
     if referral_amount > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.rewards_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.referral_token_account.to_account_info(),
                     authority: ctx.accounts.rewards_pool_config.to_account_info(),
                 },
@@ -199,6 +204,7 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
                 ]],
             ),
             referral_amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         
         // Update referral sub-pool stats
@@ -208,11 +214,12 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
     
     // Transfer to reserve sub-pool
     if reserve_amount > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.rewards_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.reserve_token_account.to_account_info(),
                     authority: ctx.accounts.rewards_pool_config.to_account_info(),
                 },
@@ -223,6 +230,7 @@ pub fn distribute_rewards(ctx: Context<DistributeRewards>) -> Result<()> {
                 ]],
             ),
             reserve_amount,
+            ctx.accounts.token_mint.decimals,
         )?;
         
         // Update reserve sub-pool stats
@@ -305,11 +313,12 @@ pub fn emergency_withdraw(
     require!(amount <= ctx.accounts.reserve_token_account.amount, ErrorCode::InsufficientFunds);
     
     // Transfer from reserve to destination
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.reserve_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.destination_token_account.to_account_info(),
                 authority: ctx.accounts.rewards_pool_config.to_account_info(),
             },
@@ -320,6 +329,7 @@ pub fn emergency_withdraw(
             ]],
         ),
         amount,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Update reserve sub-pool stats
@@ -339,7 +349,7 @@ pub struct InitializeRewardsPool<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
@@ -355,7 +365,7 @@ pub struct InitializeRewardsPool<'info> {
         token::mint = token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub rewards_vault: Account<'info, TokenAccount>,
+    pub rewards_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -363,7 +373,7 @@ pub struct InitializeRewardsPool<'info> {
         token::mint = token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub staking_token_account: Account<'info, TokenAccount>,
+    pub staking_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -371,7 +381,7 @@ pub struct InitializeRewardsPool<'info> {
         token::mint = token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub spin_token_account: Account<'info, TokenAccount>,
+    pub spin_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -379,7 +389,7 @@ pub struct InitializeRewardsPool<'info> {
         token::mint = token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub referral_token_account: Account<'info, TokenAccount>,
+    pub referral_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -387,7 +397,7 @@ pub struct InitializeRewardsPool<'info> {
         token::mint = token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub reserve_token_account: Account<'info, TokenAccount>,
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -426,7 +436,7 @@ pub struct InitializeRewardsPool<'info> {
     pub reserve_sub_pool: Account<'info, SubPool>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -447,7 +457,7 @@ pub struct DistributeRewards<'info> {
         token::mint = rewards_pool_config.token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub rewards_vault: Account<'info, TokenAccount>,
+    pub rewards_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
@@ -461,7 +471,7 @@ pub struct DistributeRewards<'info> {
         token::mint = rewards_pool_config.token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub staking_token_account: Account<'info, TokenAccount>,
+    pub staking_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
@@ -475,7 +485,7 @@ pub struct DistributeRewards<'info> {
         token::mint = rewards_pool_config.token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub spin_token_account: Account<'info, TokenAccount>,
+    pub spin_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
@@ -489,7 +499,7 @@ pub struct DistributeRewards<'info> {
         token::mint = rewards_pool_config.token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub referral_token_account: Account<'info, TokenAccount>,
+    pub referral_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
@@ -503,7 +513,7 @@ pub struct DistributeRewards<'info> {
         token::mint = rewards_pool_config.token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub reserve_token_account: Account<'info, TokenAccount>,
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         init,
@@ -518,7 +528,10 @@ pub struct DistributeRewards<'info> {
     )]
     pub distribution_history: Account<'info, DistributionHistory>,
     
-    pub token_program: Program<'info, Token>,
+    #[account(address = rewards_pool_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -587,15 +600,18 @@ pub struct EmergencyWithdraw<'info> {
         token::mint = rewards_pool_config.token_mint,
         token::authority = rewards_pool_config,
     )]
-    pub reserve_token_account: Account<'info, TokenAccount>,
+    pub reserve_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = rewards_pool_config.token_mint,
     )]
-    pub destination_token_account: Account<'info, TokenAccount>,
+    pub destination_token_account: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = rewards_pool_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[error_code]
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use solana_program::{program::invoke, system_instruction};
 use std::convert::TryInto;
 
@@ -170,11 +170,12 @@ pub fn spin_for_yield(ctx: Context<SpinForYield>) -> Result<()> {
         .unwrap() as u64;
     
     // Transfer tokens from rewards pool to user
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.rewards_pool.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
                 authority: ctx.accounts.spin_config.to_account_info(),
             },
@@ -185,6 +186,7 @@ pub fn spin_for_yield(ctx: Context<SpinForYield>) -> Result<()> {
             ]],
         ),
         tokens_earned,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Update user spin info
@@ -241,11 +243,12 @@ pub fn claim_fallback_yield(ctx: Context<ClaimFallbackYield>) -> Result<()> {
         .unwrap() as u64;
     
     // Transfer tokens from rewards pool to user
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.rewards_pool.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
                 authority: ctx.accounts.spin_config.to_account_info(),
             },
@@ -256,6 +259,7 @@ pub fn claim_fallback_yield(ctx: Context<ClaimFallbackYield>) -> Result<()> {
             ]],
         ),
         tokens_earned,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Update user spin info
@@ -275,16 +279,18 @@ pub fn activate_lucky_charm(ctx: Context<ActivateBooster>, spins: u8) -> Result<
     let booster_cost = calculate_booster_cost(BoosterType::LuckyCharm, spins);
     
     // Transfer tokens from user to rewards pool (booster payment)
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.rewards_pool.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         booster_cost,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Activate booster
@@ -304,16 +310,18 @@ pub fn activate_yield_amplifier(ctx: Context<ActivateBooster>, hours: u8) -> Res
     let booster_cost = calculate_booster_cost(BoosterType::YieldAmplifier, hours);
     
     // Transfer tokens from user to rewards pool (booster payment)
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.rewards_pool.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         booster_cost,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Activate booster
@@ -340,16 +348,18 @@ pub fn activate_chad_shield(ctx: Context<ActivateBooster>, spins: u8) -> Result<
     let booster_cost = calculate_booster_cost(BoosterType::ChadShield, spins);
     
     // Transfer tokens from user to rewards pool (booster payment)
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.rewards_pool.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         booster_cost,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Activate booster
@@ -452,7 +462,7 @@ pub struct InitializeSpinSystem<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
@@ -467,13 +477,13 @@ pub struct InitializeSpinSystem<'info> {
         mut,
         token::mint = token_mint,
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: This is the staking config account
     pub staking_config: UncheckedAccount<'info>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -521,20 +531,23 @@ pub struct SpinForYield<'info> {
         token::mint = spin_config.token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = spin_config.token_mint,
         constraint = rewards_pool.key() == spin_config.rewards_pool
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
     
     /// CHECK: Used for randomness
     pub recent_blockhashes: UncheckedAccount<'info>,
     
+    #[account(address = spin_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -566,16 +579,19 @@ pub struct ClaimFallbackYield<'info> {
         token::mint = spin_config.token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = spin_config.token_mint,
         constraint = rewards_pool.key() == spin_config.rewards_pool
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = spin_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -597,16 +613,19 @@ pub struct ActivateBooster<'info> {
         token::mint = spin_config.token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = spin_config.token_mint,
         constraint = rewards_pool.key() == spin_config.rewards_pool
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = spin_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -1,8 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use solana_program::{program::invoke, system_instruction};
 use std::convert::TryInto;
 
+use crate::TokenConfig;
+
 #[account]
 pub struct StakingConfig {
     pub authority: Pubkey,
@@ -13,10 +15,11 @@ pub struct StakingConfig {
     pub daily_yield_bps: u16, // Daily yield in basis points for non-spinners (50 = 0.5%)
     pub early_unstake_penalty_tiers: [u16; 4], // Penalties in basis points for different tiers
     pub penalty_threshold_hours: [u8; 4],      // Time thresholds for penalty tiers in hours
+    pub penalty_burn_bps: u16, // Share of early unstake penalties burned, the rest goes to the rewards pool
 }
 
 impl StakingConfig {
-    pub const LEN: usize = 32 + 32 + 32 + 8 + 2 + 2 + (2 * 4) + (1 * 4);
+    pub const LEN: usize = 32 + 32 + 32 + 8 + 2 + 2 + (2 * 4) + (1 * 4) + 2;
 }
 
 #[account]
@@ -50,6 +53,7 @@ pub fn initialize_staking(
     // Set up penalty tiers as per requirements
     staking_config.early_unstake_penalty_tiers = [5000, 3500, 1500, 0]; // 50%, 35%, 15%, 0%
     staking_config.penalty_threshold_hours = [168, 336, 720, 744]; // 7 days, 14 days, 30 days, 31+ days
+    staking_config.penalty_burn_bps = 5000; // Half of every penalty is burned
     
     Ok(())
 }
@@ -62,24 +66,50 @@ pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
     let current_time = Clock::get()?.unix_timestamp;
     
     // Transfer tokens from user to stake vault
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.user_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.stake_vault.to_account_info(),
                 authority: ctx.accounts.user.to_account_info(),
             },
         ),
         amount,
+        ctx.accounts.token_mint.decimals,
+    )?;
+    
+    // Only the amount that reached the vault after the transfer fee is staked
+    let transfer_fee = crate::transfer_fee::calculate_transfer_fee(
+        &ctx.accounts.token_mint.to_account_info(),
+        amount,
     )?;
+    let received_amount = amount.checked_sub(transfer_fee).unwrap();
     
+    add_stake(
+        staking_config,
+        stake_account,
+        ctx.accounts.user.key(),
+        received_amount,
+        current_time,
+    )
+}
+
+// Helper function to credit tokens that reached the stake vault to a stake account
+pub fn add_stake(
+    staking_config: &mut StakingConfig,
+    stake_account: &mut StakeAccount,
+    owner: Pubkey,
+    received_amount: u64,
+    current_time: i64,
+) -> Result<()> {
     // Initialize stake account if new
     if stake_account.owner == Pubkey::default() {
-        stake_account.owner = ctx.accounts.user.key();
+        stake_account.owner = owner;
         stake_account.stake_timestamp = current_time;
         stake_account.last_claim_timestamp = current_time;
-        stake_account.stake_amount = amount;
+        stake_account.stake_amount = received_amount;
         stake_account.cumulative_rewards = 0;
     } else {
         // If already staking, calculate pending rewards first
@@ -91,13 +121,13 @@ pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
         )?;
         
         // Update stake account
-        stake_account.stake_amount = stake_account.stake_amount.checked_add(amount).unwrap();
+        stake_account.stake_amount = stake_account.stake_amount.checked_add(received_amount).unwrap();
         stake_account.last_claim_timestamp = current_time;
         stake_account.cumulative_rewards = stake_account.cumulative_rewards.checked_add(pending_rewards).unwrap();
     }
     
     // Update total staked in config
-    staking_config.total_staked = staking_config.total_staked.checked_add(amount).unwrap();
+    staking_config.total_staked = staking_config.total_staked.checked_add(received_amount).unwrap();
     
     Ok(())
 }
@@ -105,6 +135,12 @@ pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
 pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
     require!(amount > 0, ErrorCode::InvalidAmount);
     
+    // The staking config signs the vault transfers and burns while it is borrowed mutably below
+    let staking_config_info = ctx.accounts.staking_config.to_account_info();
+    let token_mint_key = ctx.accounts.staking_config.token_mint;
+    let staking_config_bump = *ctx.bumps.get("staking_config").unwrap();
+    let signer_seeds: &[&[u8]] = &[b"staking".as_ref(), token_mint_key.as_ref(), &[staking_config_bump]];
+    
     let staking_config = &mut ctx.accounts.staking_config;
     let stake_account = &mut ctx.accounts.stake_account;
     let current_time = Clock::get()?.unix_timestamp;
@@ -136,40 +172,56 @@ pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
     let return_amount = amount.checked_sub(penalty_amount).unwrap();
     
     // Transfer tokens from stake vault to user
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.stake_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
-                authority: ctx.accounts.staking_config.to_account_info(),
+                authority: staking_config_info.clone(),
             },
-            &[&[
-                b"staking".as_ref(),
-                staking_config.token_mint.as_ref(),
-                &[ctx.bumps.staking_config],
-            ]],
+            &[signer_seeds],
         ),
         return_amount,
+        ctx.accounts.token_mint.decimals,
     )?;
     
-    // If there's a penalty, transfer it to the rewards pool
-    if penalty_amount > 0 {
-        token::transfer(
+    // Burn part of the penalty, the rest goes to the rewards pool
+    let penalty_burn_amount = (penalty_amount as u128)
+        .checked_mul(staking_config.penalty_burn_bps as u128)
+        .unwrap()
+        .checked_div(10000)
+        .unwrap() as u64;
+    let penalty_pool_amount = penalty_amount.checked_sub(penalty_burn_amount).unwrap();
+    
+    if penalty_burn_amount > 0 {
+        crate::burn_and_record(
+            &ctx.accounts.token_program.to_account_info(),
+            &ctx.accounts.token_mint.to_account_info(),
+            &ctx.accounts.stake_vault.to_account_info(),
+            &staking_config_info,
+            &[signer_seeds],
+            &mut ctx.accounts.token_config,
+            penalty_burn_amount,
+            TokenConfig::BURN_SOURCE_PENALTY,
+        )?;
+    }
+    
+    if penalty_pool_amount > 0 {
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                Transfer {
+                TransferChecked {
                     from: ctx.accounts.stake_vault.to_account_info(),
+                    mint: ctx.accounts.token_mint.to_account_info(),
                     to: ctx.accounts.rewards_pool.to_account_info(),
-                    authority: ctx.accounts.staking_config.to_account_info(),
+                    authority: staking_config_info.clone(),
                 },
-                &[&[
-                    b"staking".as_ref(),
-                    staking_config.token_mint.as_ref(),
-                    &[ctx.bumps.staking_config],
-                ]],
+                &[signer_seeds],
             ),
-            penalty_amount,
+            penalty_pool_amount,
+            ctx.accounts.token_mint.decimals,
         )?;
     }
     
@@ -206,11 +258,12 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
     require!(pending_rewards > 0, ErrorCode::NoRewardsToClaim);
     
     // Transfer rewards from rewards pool to user
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.rewards_pool.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info(),
                 authority: ctx.accounts.staking_config.to_account_info(),
             },
@@ -221,6 +274,7 @@ pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
             ]],
         ),
         pending_rewards,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Update stake account
@@ -291,7 +345,7 @@ pub struct InitializeStaking<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
@@ -308,16 +362,16 @@ pub struct InitializeStaking<'info> {
         token::mint = token_mint,
         token::authority = staking_config,
     )]
-    pub stake_vault: Account<'info, TokenAccount>,
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = token_mint,
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -343,17 +397,20 @@ pub struct Stake<'info> {
         token::mint = staking_config.token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = staking_config.token_mint,
         token::authority = staking_config,
     )]
-    pub stake_vault: Account<'info, TokenAccount>,
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = staking_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -382,24 +439,37 @@ pub struct Unstake<'info> {
         token::mint = staking_config.token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = staking_config.token_mint,
         token::authority = staking_config,
     )]
-    pub stake_vault: Account<'info, TokenAccount>,
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = staking_config.token_mint,
         constraint = rewards_pool.key() == staking_config.rewards_pool
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
+    
+    // Records the burned part of the penalty
+    #[account(
+        mut,
+        constraint = token_config.mint == staking_config.token_mint
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+    
+    #[account(
+        mut,
+        address = staking_config.token_mint
+    )]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
@@ -426,16 +496,19 @@ pub struct ClaimRewards<'info> {
         token::mint = staking_config.token_mint,
         token::authority = user,
     )]
-    pub user_token_account: Account<'info, TokenAccount>,
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = staking_config.token_mint,
         constraint = rewards_pool.key() == staking_config.rewards_pool
     )]
-    pub rewards_pool: Account<'info, TokenAccount>,
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
+    
+    #[account(address = staking_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
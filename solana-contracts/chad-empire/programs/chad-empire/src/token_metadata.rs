@@ -0,0 +1,249 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use solana_program::program::{invoke, invoke_signed};
+
+use crate::TokenConfig;
+
+// Metaplex Token Metadata program
+pub mod metadata_program {
+    use anchor_lang::declare_id;
+    declare_id!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+}
+
+// Metaplex limits on the metadata fields
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+
+// Metaplex instruction indexes
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+// Borsh layouts of the Metaplex instruction arguments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Uses {
+    pub use_method: u8,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub enum CollectionDetails {
+    V1 { size: u64 },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct CreateMetadataAccountArgsV3 {
+    data: DataV2,
+    is_mutable: bool,
+    collection_details: Option<CollectionDetails>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+struct UpdateMetadataAccountArgsV2 {
+    data: Option<DataV2>,
+    update_authority: Option<Pubkey>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+}
+
+pub fn update_token_metadata(
+    ctx: Context<UpdateTokenMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+        ErrorCode::Unauthorized
+    );
+    require!(!ctx.accounts.token_config.metadata_locked, ErrorCode::MetadataLocked);
+
+    let data = token_data(name, symbol, uri)?;
+    update_metadata_account(&ctx, Some(data), None, *ctx.bumps.get("metadata_authority").unwrap())
+}
+
+pub fn lock_token_metadata(ctx: Context<UpdateTokenMetadata>) -> Result<()> {
+    require!(
+        ctx.accounts.authority.key() == ctx.accounts.token_config.authority,
+        ErrorCode::Unauthorized
+    );
+    require!(!ctx.accounts.token_config.metadata_locked, ErrorCode::MetadataLocked);
+
+    // Also make the Metaplex account immutable so the lock holds outside this program
+    update_metadata_account(&ctx, None, Some(false), *ctx.bumps.get("metadata_authority").unwrap())?;
+
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.metadata_locked = true;
+
+    Ok(())
+}
+
+// Helper function to build the metadata of a fungible token
+pub fn token_data(name: String, symbol: String, uri: String) -> Result<DataV2> {
+    require!(name.len() <= MAX_NAME_LENGTH, ErrorCode::MetadataTooLong);
+    require!(symbol.len() <= MAX_SYMBOL_LENGTH, ErrorCode::MetadataTooLong);
+    require!(uri.len() <= MAX_URI_LENGTH, ErrorCode::MetadataTooLong);
+
+    Ok(DataV2 {
+        name,
+        symbol,
+        uri,
+        seller_fee_basis_points: 0,
+        creators: None,
+        collection: None,
+        uses: None,
+    })
+}
+
+// Helper function to create the Metaplex metadata account of the mint
+// The update authority is the metadata_authority PDA, so only this program can change it
+#[allow(clippy::too_many_arguments)]
+pub fn create_metadata_account<'info>(
+    metadata: &UncheckedAccount<'info>,
+    mint: &Signer<'info>,
+    mint_authority: &Signer<'info>,
+    payer: &Signer<'info>,
+    metadata_authority: &UncheckedAccount<'info>,
+    metadata_program: &UncheckedAccount<'info>,
+    system_program: &Program<'info, System>,
+    rent: &Sysvar<'info, Rent>,
+    data: DataV2,
+) -> Result<()> {
+    let args = CreateMetadataAccountArgsV3 {
+        data,
+        is_mutable: true,
+        collection_details: None,
+    };
+    let mut instruction_data = vec![CREATE_METADATA_ACCOUNT_V3];
+    instruction_data.extend(args.try_to_vec()?);
+
+    invoke(
+        &Instruction {
+            program_id: metadata_program.key(),
+            accounts: vec![
+                AccountMeta::new(metadata.key(), false),
+                AccountMeta::new_readonly(mint.key(), false),
+                AccountMeta::new_readonly(mint_authority.key(), true),
+                AccountMeta::new(payer.key(), true),
+                AccountMeta::new_readonly(metadata_authority.key(), false),
+                AccountMeta::new_readonly(system_program.key(), false),
+                AccountMeta::new_readonly(rent.key(), false),
+            ],
+            data: instruction_data,
+        },
+        &[
+            metadata.to_account_info(),
+            mint.to_account_info(),
+            mint_authority.to_account_info(),
+            payer.to_account_info(),
+            metadata_authority.to_account_info(),
+            system_program.to_account_info(),
+            rent.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+// Helper function to update the Metaplex metadata account, signed by the metadata_authority PDA
+fn update_metadata_account(
+    ctx: &Context<UpdateTokenMetadata>,
+    data: Option<DataV2>,
+    is_mutable: Option<bool>,
+    metadata_authority_bump: u8,
+) -> Result<()> {
+    let args = UpdateMetadataAccountArgsV2 {
+        data,
+        update_authority: None,
+        primary_sale_happened: None,
+        is_mutable,
+    };
+    let mut instruction_data = vec![UPDATE_METADATA_ACCOUNT_V2];
+    instruction_data.extend(args.try_to_vec()?);
+
+    let mint_key = ctx.accounts.token_config.mint;
+    invoke_signed(
+        &Instruction {
+            program_id: ctx.accounts.metadata_program.key(),
+            accounts: vec![
+                AccountMeta::new(ctx.accounts.metadata.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.metadata_authority.key(), true),
+            ],
+            data: instruction_data,
+        },
+        &[
+            ctx.accounts.metadata.to_account_info(),
+            ctx.accounts.metadata_authority.to_account_info(),
+        ],
+        &[&[
+            b"metadata_authority".as_ref(),
+            mint_key.as_ref(),
+            &[metadata_authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateTokenMetadata<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub token_config: Account<'info, TokenConfig>,
+
+    /// CHECK: Metaplex metadata account of the mint, validated by the metadata program
+    #[account(
+        mut,
+        seeds = [b"metadata".as_ref(), metadata_program.key().as_ref(), token_config.mint.as_ref()],
+        bump,
+        seeds::program = metadata_program.key()
+    )]
+    pub metadata: UncheckedAccount<'info>,
+
+    /// CHECK: PDA set as the update authority of the metadata account
+    #[account(
+        seeds = [b"metadata_authority".as_ref(), token_config.mint.as_ref()],
+        bump
+    )]
+    pub metadata_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Metaplex Token Metadata program
+    #[account(address = metadata_program::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("You are not authorized to perform this action")]
+    Unauthorized,
+    #[msg("Token metadata has been locked")]
+    MetadataLocked,
+    #[msg("Token name, symbol or URI is too long")]
+    MetadataTooLong,
+}
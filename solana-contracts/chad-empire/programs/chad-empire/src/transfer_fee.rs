@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::{instruction as transfer_fee_instruction, TransferFeeConfig},
+        BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+    },
+};
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{self, Mint, TokenAccount};
+use solana_program::{
+    program::{invoke, invoke_signed},
+    system_instruction,
+};
+
+use crate::TokenConfig;
+
+pub fn harvest_transfer_fees<'info>(
+    ctx: Context<'_, '_, '_, 'info, HarvestTransferFees<'info>>,
+) -> Result<()> {
+    let token_program = ctx.accounts.token_program.key();
+    let mint_key = ctx.accounts.mint.key();
+
+    // Move fees withheld in the given token accounts into the mint
+    // Harvesting to the mint is permissionless, so any account can be swept
+    if !ctx.remaining_accounts.is_empty() {
+        let sources: Vec<&Pubkey> = ctx.remaining_accounts.iter().map(|a| a.key).collect();
+        let mut account_infos = vec![ctx.accounts.mint.to_account_info()];
+        account_infos.extend(ctx.remaining_accounts.iter().cloned());
+
+        invoke(
+            &transfer_fee_instruction::harvest_withheld_tokens_to_mint(
+                &token_program,
+                &mint_key,
+                &sources,
+            )?,
+            &account_infos,
+        )?;
+    }
+
+    // Read the total amount now withheld in the mint
+    let withheld_amount = {
+        let mint_info = ctx.accounts.mint.to_account_info();
+        let mint_data = mint_info.try_borrow_data()?;
+        let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+        let fee_config = mint_state.get_extension::<TransferFeeConfig>()?;
+        u64::from(fee_config.withheld_amount)
+    };
+
+    require!(withheld_amount > 0, ErrorCode::NoFeesToHarvest);
+
+    // Withdraw everything withheld in the mint into the rewards pool
+    invoke_signed(
+        &transfer_fee_instruction::withdraw_withheld_tokens_from_mint(
+            &token_program,
+            &mint_key,
+            &ctx.accounts.rewards_pool.key(),
+            &ctx.accounts.fee_authority.key(),
+            &[],
+        )?,
+        &[
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.rewards_pool.to_account_info(),
+            ctx.accounts.fee_authority.to_account_info(),
+        ],
+        &[&[
+            b"fee_authority".as_ref(),
+            mint_key.as_ref(),
+            &[*ctx.bumps.get("fee_authority").unwrap()],
+        ]],
+    )?;
+
+    // Update harvest stats
+    let token_config = &mut ctx.accounts.token_config;
+    token_config.total_fees_harvested = token_config
+        .total_fees_harvested
+        .checked_add(withheld_amount)
+        .unwrap();
+
+    Ok(())
+}
+
+// Helper function to create the CHAD mint with the transfer fee extension
+pub fn create_fee_mint<'info>(
+    payer: &Signer<'info>,
+    mint: &Signer<'info>,
+    fee_authority: &UncheckedAccount<'info>,
+    token_program: &Program<'info, Token2022>,
+    system_program: &Program<'info, System>,
+    decimals: u8,
+    transfer_fee_bps: u16,
+) -> Result<()> {
+    let space = ExtensionType::get_account_len::<spl_token_2022::state::Mint>(&[
+        ExtensionType::TransferFeeConfig,
+    ]);
+
+    invoke(
+        &system_instruction::create_account(
+            payer.key,
+            mint.key,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            token_program.key,
+        ),
+        &[
+            payer.to_account_info(),
+            mint.to_account_info(),
+            system_program.to_account_info(),
+        ],
+    )?;
+
+    // The extension has to be configured before the mint is initialized
+    invoke(
+        &transfer_fee_instruction::initialize_transfer_fee_config(
+            token_program.key,
+            mint.key,
+            Some(fee_authority.key),
+            Some(fee_authority.key),
+            transfer_fee_bps,
+            u64::MAX, // No cap on the fee per transfer
+        )?,
+        &[mint.to_account_info()],
+    )?;
+
+    token_interface::initialize_mint2(
+        CpiContext::new(
+            token_program.to_account_info(),
+            token_interface::InitializeMint2 {
+                mint: mint.to_account_info(),
+            },
+        ),
+        decimals,
+        payer.key,
+        Some(payer.key),
+    )?;
+
+    Ok(())
+}
+
+// Helper function to create a token account of the fee mint
+pub fn create_fee_token_account<'info>(
+    payer: &Signer<'info>,
+    account: &Signer<'info>,
+    mint: &Signer<'info>,
+    owner: &Signer<'info>,
+    token_program: &Program<'info, Token2022>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    // Accounts of a fee mint must have room to track their withheld fees
+    let space = ExtensionType::get_account_len::<spl_token_2022::state::Account>(&[
+        ExtensionType::TransferFeeAmount,
+    ]);
+
+    invoke(
+        &system_instruction::create_account(
+            payer.key,
+            account.key,
+            Rent::get()?.minimum_balance(space),
+            space as u64,
+            token_program.key,
+        ),
+        &[
+            payer.to_account_info(),
+            account.to_account_info(),
+            system_program.to_account_info(),
+        ],
+    )?;
+
+    token_interface::initialize_account3(CpiContext::new(
+        token_program.to_account_info(),
+        token_interface::InitializeAccount3 {
+            account: account.to_account_info(),
+            mint: mint.to_account_info(),
+            authority: owner.to_account_info(),
+        },
+    ))?;
+
+    Ok(())
+}
+
+// Helper function to update the fee withheld by the mint, signed by the fee_authority PDA
+// Token-2022 applies a new fee two epochs after it is set
+pub fn set_mint_transfer_fee<'info>(
+    mint: &InterfaceAccount<'info, Mint>,
+    fee_authority: &UncheckedAccount<'info>,
+    fee_authority_bump: u8,
+    token_program: &Program<'info, Token2022>,
+    transfer_fee_bps: u16,
+) -> Result<()> {
+    let mint_key = mint.key();
+    invoke_signed(
+        &transfer_fee_instruction::set_transfer_fee(
+            token_program.key,
+            &mint_key,
+            fee_authority.key,
+            &[],
+            transfer_fee_bps,
+            u64::MAX,
+        )?,
+        &[mint.to_account_info(), fee_authority.to_account_info()],
+        &[&[
+            b"fee_authority".as_ref(),
+            mint_key.as_ref(),
+            &[fee_authority_bump],
+        ]],
+    )?;
+
+    Ok(())
+}
+
+// Helper function to transfer without paying the mint's transfer fee, for exempt flows
+// Fees already withheld in the destination are harvested to the mint first,
+// so only the fee of this transfer is handed back to the destination
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_checked_without_fee<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    fee_authority: &AccountInfo<'info>,
+    fee_authority_bump: u8,
+    amount: u64,
+    decimals: u8,
+) -> Result<()> {
+    let transfer_fee = calculate_transfer_fee(mint, amount)?;
+
+    if transfer_fee > 0 {
+        invoke(
+            &transfer_fee_instruction::harvest_withheld_tokens_to_mint(
+                token_program.key,
+                mint.key,
+                &[to.key],
+            )?,
+            &[mint.clone(), to.clone()],
+        )?;
+    }
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            token_interface::TransferChecked {
+                from: from.clone(),
+                mint: mint.clone(),
+                to: to.clone(),
+                authority: authority.clone(),
+            },
+            signer_seeds,
+        ),
+        amount,
+        decimals,
+    )?;
+
+    // The destination is its own source, so the fee withheld by this transfer is released to it
+    if transfer_fee > 0 {
+        let mint_key = mint.key();
+        invoke_signed(
+            &transfer_fee_instruction::withdraw_withheld_tokens_from_accounts(
+                token_program.key,
+                mint.key,
+                to.key,
+                fee_authority.key,
+                &[],
+                &[to.key],
+            )?,
+            &[mint.clone(), to.clone(), fee_authority.clone()],
+            &[&[
+                b"fee_authority".as_ref(),
+                mint_key.as_ref(),
+                &[fee_authority_bump],
+            ]],
+        )?;
+    }
+
+    Ok(())
+}
+
+// Helper function to read the supply of a mint, with or without extensions
+pub fn get_mint_supply(mint: &AccountInfo) -> Result<u64> {
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    Ok(mint_state.base.supply)
+}
+
+// Helper function to calculate the fee the mint withholds on a transfer
+// Reads the fee in force for the current epoch, which lags a fee change by two epochs
+pub fn calculate_transfer_fee(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    if *mint.owner != Token2022::id() {
+        return Ok(0);
+    }
+
+    let mint_data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_data)?;
+    let fee = match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(fee_config) => fee_config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ErrorCode::FeeCalculationFailed)?,
+        Err(_) => 0,
+    };
+
+    Ok(fee)
+}
+
+#[derive(Accounts)]
+pub struct HarvestTransferFees<'info> {
+    pub harvester: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = token_config.mint == mint.key()
+    )]
+    pub token_config: Account<'info, TokenConfig>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: PDA set as the withdraw authority of the mint
+    #[account(
+        seeds = [b"fee_authority".as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub fee_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = rewards_pool.key() == token_config.rewards_pool
+    )]
+    pub rewards_pool: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token2022>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("No withheld fees to harvest")]
+    NoFeesToHarvest,
+    #[msg("Transfer fee calculation failed")]
+    FeeCalculationFailed,
+}
@@ -1,5 +1,5 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked};
 use solana_program::{program::invoke, system_instruction};
 use std::convert::TryInto;
 
@@ -58,16 +58,18 @@ pub fn initialize_vesting(
     vesting_config.total_claimed = 0;
     
     // Transfer tokens to vesting vault
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.authority_token_account.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.vesting_vault.to_account_info(),
                 authority: ctx.accounts.authority.to_account_info(),
             },
         ),
         total_allocation,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     Ok(())
@@ -116,11 +118,12 @@ pub fn claim_vested_tokens(ctx: Context<ClaimVestedTokens>) -> Result<()> {
     require!(claimable_amount > 0, ErrorCode::NoTokensToClaim);
     
     // Transfer tokens from vesting vault to recipient
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: ctx.accounts.vesting_vault.to_account_info(),
+                mint: ctx.accounts.token_mint.to_account_info(),
                 to: ctx.accounts.recipient_token_account.to_account_info(),
                 authority: ctx.accounts.vesting_config.to_account_info(),
             },
@@ -131,6 +134,7 @@ pub fn claim_vested_tokens(ctx: Context<ClaimVestedTokens>) -> Result<()> {
             ]],
         ),
         claimable_amount,
+        ctx.accounts.token_mint.decimals,
     )?;
     
     // Update recipient info
@@ -177,7 +181,7 @@ pub fn update_vesting_schedule(
 }
 
 // Helper function to calculate claimable amount
-fn calculate_claimable_amount(
+pub fn calculate_claimable_amount(
     allocation_amount: u64,
     claimed_amount: u64,
     tge_release_bps: u16,
@@ -253,7 +257,7 @@ pub struct InitializeVesting<'info> {
     #[account(mut)]
     pub authority: Signer<'info>,
     
-    pub token_mint: Account<'info, Mint>,
+    pub token_mint: InterfaceAccount<'info, Mint>,
     
     #[account(
         init,
@@ -270,17 +274,17 @@ pub struct InitializeVesting<'info> {
         token::mint = token_mint,
         token::authority = vesting_config,
     )]
-    pub vesting_vault: Account<'info, TokenAccount>,
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = token_mint,
         token::authority = authority,
     )]
-    pub authority_token_account: Account<'info, TokenAccount>,
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
     
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub rent: Sysvar<'info, Rent>,
 }
 
@@ -331,16 +335,19 @@ pub struct ClaimVestedTokens<'info> {
         token::mint = vesting_config.token_mint,
         token::authority = vesting_config,
     )]
-    pub vesting_vault: Account<'info, TokenAccount>,
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
     
     #[account(
         mut,
         token::mint = vesting_config.token_mint,
         token::authority = recipient,
     )]
-    pub recipient_token_account: Account<'info, TokenAccount>,
+    pub recipient_token_account: InterfaceAccount<'info, TokenAccount>,
     
-    pub token_program: Program<'info, Token>,
+    #[account(address = vesting_config.token_mint)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+    
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 #[derive(Accounts)]
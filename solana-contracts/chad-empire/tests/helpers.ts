@@ -0,0 +1,85 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import { TOKEN_2022_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { ChadEmpire } from "../target/types/chad_empire";
+
+export const CHAD_EMPIRE_PROGRAM_ID = new PublicKey("Fg6PaFpoGXkYsidMpWTK6W2BeZ7FEfcYkg476zPFsLnS");
+
+// Address of the Metaplex Token Metadata program, served by the stand-in loaded from Anchor.toml
+export const METADATA_PROGRAM_ID = new PublicKey("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+export const DECIMALS = 9;
+
+// Transfer fee the mint is created with, the lower of the buy and sell tax
+export const TRANSFER_FEE_BPS = 1000;
+
+// Fee Token-2022 withholds on a plain transfer of amount, rounded up
+export function transferFee(amount: BN): BN {
+  return amount.muln(TRANSFER_FEE_BPS).addn(9999).divn(10000);
+}
+
+// Smallest amount to send so that at least amount arrives after the transfer fee
+export function withTransferFee(amount: BN): BN {
+  return amount.muln(10000).divn(10000 - TRANSFER_FEE_BPS).addn(1);
+}
+
+export interface ChadToken {
+  mint: Keypair;
+  tokenConfig: Keypair;
+  authorityTokenAccount: Keypair;
+  rewardsPool: Keypair;
+  feeAuthority: PublicKey;
+  metadata: PublicKey;
+  metadataAuthority: PublicKey;
+}
+
+export function findPda(seeds: (Buffer | Uint8Array)[], programId: PublicKey): PublicKey {
+  return PublicKey.findProgramAddressSync(seeds, programId)[0];
+}
+
+// Creates the CHAD mint, its token config and its metadata account
+export async function initializeToken(
+  program: Program<ChadEmpire>,
+  name = "ChadEmpire",
+  symbol = "CHAD",
+  uri = "https://chadempire.io/token.json"
+): Promise<ChadToken> {
+  const authority = program.provider.publicKey;
+  const mint = Keypair.generate();
+  const tokenConfig = Keypair.generate();
+  const authorityTokenAccount = Keypair.generate();
+  const rewardsPool = Keypair.generate();
+
+  const feeAuthority = findPda([Buffer.from("fee_authority"), mint.publicKey.toBuffer()], program.programId);
+  const metadata = findPda(
+    [Buffer.from("metadata"), METADATA_PROGRAM_ID.toBuffer(), mint.publicKey.toBuffer()],
+    METADATA_PROGRAM_ID
+  );
+  const metadataAuthority = findPda(
+    [Buffer.from("metadata_authority"), mint.publicKey.toBuffer()],
+    program.programId
+  );
+
+  await program.methods
+    .initializeToken(DECIMALS, name, symbol, uri)
+    .accounts({
+      authority,
+      mint: mint.publicKey,
+      authorityTokenAccount: authorityTokenAccount.publicKey,
+      rewardsPool: rewardsPool.publicKey,
+      feeAuthority,
+      metadata,
+      metadataAuthority,
+      metadataProgram: METADATA_PROGRAM_ID,
+      tokenConfig: tokenConfig.publicKey,
+      systemProgram: SystemProgram.programId,
+      tokenProgram: TOKEN_2022_PROGRAM_ID,
+      rent: SYSVAR_RENT_PUBKEY,
+    })
+    .signers([mint, tokenConfig, authorityTokenAccount, rewardsPool])
+    .preInstructions([anchor.web3.ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
+    .rpc();
+
+  return { mint, tokenConfig, authorityTokenAccount, rewardsPool, feeAuthority, metadata, metadataAuthority };
+}
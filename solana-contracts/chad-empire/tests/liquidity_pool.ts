@@ -0,0 +1,393 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import {
+  createAssociatedTokenAccountIdempotentInstruction,
+  createTransferCheckedInstruction,
+  getAssociatedTokenAddressSync,
+  TOKEN_2022_PROGRAM_ID,
+  unpackAccount,
+} from "@solana/spl-token";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  SYSVAR_RENT_PUBKEY,
+  SYSVAR_SLOT_HASHES_PUBKEY,
+  Transaction,
+} from "@solana/web3.js";
+import { BankrunProvider, startAnchor } from "anchor-bankrun";
+import { expect } from "chai";
+import { keccak_256 } from "js-sha3";
+import { Clock, ProgramTestContext } from "solana-bankrun";
+import { ChadEmpire, IDL } from "../target/types/chad_empire";
+import {
+  CHAD_EMPIRE_PROGRAM_ID,
+  ChadToken,
+  DECIMALS,
+  findPda,
+  initializeToken,
+  METADATA_PROGRAM_ID,
+  transferFee,
+  withTransferFee,
+} from "./helpers";
+
+// Runs the launch path on bankrun, whose clock can be moved past the investment days:
+// fair launch -> create_liquidity seeds the pool -> swap -> add_liquidity -> remove_liquidity
+describe("liquidity pool", () => {
+  const UNIT = new BN(10).pow(new BN(DECIMALS));
+  const DAY_SUPPLY = new BN(100_000_000).mul(UNIT);
+  const LIQUIDITY_TOKENS = new BN(300_000_000).mul(UNIT);
+  const CONTRIBUTION = new BN(10 * LAMPORTS_PER_SOL);
+  const SWAP_FEE_BPS = 30;
+  const ROUND_ID = 0;
+  const SEED = Buffer.alloc(32, 7);
+
+  let context: ProgramTestContext;
+  let provider: BankrunProvider;
+  let program: Program<ChadEmpire>;
+  let authority: PublicKey;
+  let token: ChadToken;
+
+  let fairLaunchConfig: PublicKey;
+  let solVault: PublicKey;
+  let dailyStats: PublicKey;
+  let ammPool: PublicKey;
+  let poolSolVault: PublicKey;
+  let feeAuthority: PublicKey;
+  const tokenVault = Keypair.generate();
+  const poolTokenVault = Keypair.generate();
+  const lpMint = Keypair.generate();
+  let userTokenAccount: PublicKey;
+  let userLpAccount: PublicKey;
+  let startTime: number;
+
+  const now = async () => Number((await context.banksClient.getClock()).unixTimestamp);
+
+  // Moves to a later slot, so new SlotHashes entries exist, and sets the clock time
+  const warpTo = async (unixTimestamp: number) => {
+    const clock = await context.banksClient.getClock();
+    context.warpToSlot(clock.slot + BigInt(1));
+    context.setClock(
+      new Clock(clock.slot + BigInt(1), clock.epochStartTimestamp, clock.epoch, clock.leaderScheduleEpoch, BigInt(unixTimestamp))
+    );
+  };
+
+  const tokenBalance = async (address: PublicKey) => {
+    const account = await context.banksClient.getAccount(address);
+    const info = { ...account, data: Buffer.from(account.data) };
+    return new BN(unpackAccount(address, info as any, TOKEN_2022_PROGRAM_ID).amount.toString());
+  };
+
+  const lamports = async (address: PublicKey) => new BN((await context.banksClient.getBalance(address)).toString());
+
+  const send = async (...instructions: anchor.web3.TransactionInstruction[]) => {
+    await provider.sendAndConfirm(new Transaction().add(...instructions));
+  };
+
+  const expectError = async (promise: Promise<unknown>, code: string) => {
+    try {
+      await promise;
+      expect.fail(`expected ${code}`);
+    } catch (err) {
+      expect(err.error?.errorCode?.code ?? err.toString()).to.include(code);
+    }
+  };
+
+  const swapAccounts = () => ({
+    user: authority,
+    ammPool,
+    poolTokenVault: poolTokenVault.publicKey,
+    poolSolVault,
+    userTokenAccount,
+    tokenConfig: token.tokenConfig.publicKey,
+    rewardsPool: token.rewardsPool.publicKey,
+    liquidityAccumulator: token.rewardsPool.publicKey,
+    treasury: token.rewardsPool.publicKey,
+    feeAuthority,
+    userExemption: null,
+    tokenMint: token.mint.publicKey,
+    systemProgram: SystemProgram.programId,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+  });
+
+  const liquidityAccounts = () => ({
+    user: authority,
+    ammPool,
+    poolTokenVault: poolTokenVault.publicKey,
+    poolSolVault,
+    lpMint: lpMint.publicKey,
+    userTokenAccount,
+    userLpAccount,
+    tokenMint: token.mint.publicKey,
+    systemProgram: SystemProgram.programId,
+    tokenProgram: TOKEN_2022_PROGRAM_ID,
+  });
+
+  before(async () => {
+    context = await startAnchor(".", [{ name: "metadata_stub", programId: METADATA_PROGRAM_ID }], []);
+    provider = new BankrunProvider(context);
+    program = new Program<ChadEmpire>(IDL, CHAD_EMPIRE_PROGRAM_ID, provider);
+    authority = provider.wallet.publicKey;
+    token = await initializeToken(program);
+
+    const mint = token.mint.publicKey;
+    feeAuthority = findPda([Buffer.from("fee_authority"), mint.toBuffer()], program.programId);
+    fairLaunchConfig = findPda(
+      [Buffer.from("fair_launch"), mint.toBuffer(), new BN(ROUND_ID).toArrayLike(Buffer, "le", 2)],
+      program.programId
+    );
+    solVault = findPda([Buffer.from("sol_vault"), fairLaunchConfig.toBuffer()], program.programId);
+    dailyStats = findPda([Buffer.from("daily_stats"), Buffer.from([1]), fairLaunchConfig.toBuffer()], program.programId);
+    ammPool = findPda([Buffer.from("amm_pool"), mint.toBuffer()], program.programId);
+    poolSolVault = findPda([Buffer.from("amm_sol_vault"), ammPool.toBuffer()], program.programId);
+    userTokenAccount = token.authorityTokenAccount.publicKey;
+    userLpAccount = getAssociatedTokenAddressSync(lpMint.publicKey, authority, false, TOKEN_2022_PROGRAM_ID);
+
+    // A one-day launch starting shortly, with a fixed day supply
+    startTime = (await now()) + 60;
+    await program.methods
+      .initializeFairLaunch(ROUND_ID, new BN(startTime), 1, 0, new BN(0), new BN(0), new BN(0))
+      .accounts({
+        authority,
+        tokenMint: mint,
+        fairLaunchConfig,
+        solVault,
+        tokenVault: tokenVault.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([tokenVault])
+      .rpc();
+    await program.methods
+      .initDailyStats(1, DAY_SUPPLY, DAY_SUPPLY.addn(1))
+      .accounts({ authority, fairLaunchConfig, dailyStats, systemProgram: SystemProgram.programId })
+      .rpc();
+    await program.methods
+      .commitDailySupply(1, Array.from(Buffer.from(keccak_256.arrayBuffer(SEED))))
+      .accounts({ authority, fairLaunchConfig, dailyStats })
+      .rpc();
+
+    // The token vault backs the day supply and the pool's initial liquidity, after the mint's transfer fee
+    await send(
+      createTransferCheckedInstruction(
+        userTokenAccount,
+        mint,
+        tokenVault.publicKey,
+        authority,
+        BigInt(withTransferFee(DAY_SUPPLY.add(LIQUIDITY_TOKENS)).toString()),
+        DECIMALS,
+        [],
+        TOKEN_2022_PROGRAM_ID
+      )
+    );
+
+    await program.methods
+      .initializeAmmPool(SWAP_FEE_BPS)
+      .accounts({
+        authority,
+        tokenConfig: token.tokenConfig.publicKey,
+        tokenMint: mint,
+        ammPool,
+        poolTokenVault: poolTokenVault.publicKey,
+        poolSolVault,
+        lpMint: lpMint.publicKey,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        rent: SYSVAR_RENT_PUBKEY,
+      })
+      .signers([poolTokenVault, lpMint])
+      .rpc();
+
+    await warpTo(startTime + 10);
+    await program.methods
+      .contribute(CONTRIBUTION, 1, null, new BN(0), [])
+      .accounts({
+        investor: authority,
+        fairLaunchConfig,
+        dailyStats,
+        investorInfo: findPda([Buffer.from("investor"), authority.toBuffer(), fairLaunchConfig.toBuffer()], program.programId),
+        referralInfo: null,
+        solVault,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+  });
+
+  it("rejects swaps before the pool is seeded", async () => {
+    await expectError(
+      program.methods.swap(new BN(LAMPORTS_PER_SOL), new BN(0), true).accounts(swapAccounts()).rpc(),
+      "PoolNotSeeded"
+    );
+  });
+
+  it("seeds the pool and opens trading in create_liquidity", async () => {
+    // Pass the supply slot fixed at commit time, so its SlotHashes entry exists
+    const { supplySlot } = await program.account.dailyStats.fetch(dailyStats);
+    context.warpToSlot(BigInt(supplySlot.addn(1).toString()));
+    await warpTo(startTime + 86400 + 1);
+    await program.methods
+      .generateDailySupply(1, Array.from(SEED))
+      .accounts({
+        keeper: authority,
+        fairLaunchConfig,
+        dailyStats,
+        slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+        solVault,
+        tokenVault: tokenVault.publicKey,
+        tokenMint: token.mint.publicKey,
+        keeperTokenAccount: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const lpLock = findPda([Buffer.from("lp_lock"), lpMint.publicKey.toBuffer(), fairLaunchConfig.toBuffer()], program.programId);
+    await program.methods
+      .createLiquidity()
+      .accounts({
+        keeper: authority,
+        fairLaunchConfig,
+        solVault,
+        tokenVault: tokenVault.publicKey,
+        tokenConfig: token.tokenConfig.publicKey,
+        tokenMint: token.mint.publicKey,
+        ammPool,
+        poolTokenVault: poolTokenVault.publicKey,
+        poolSolVault,
+        lpMint: lpMint.publicKey,
+        lpLock,
+        lpLockVault: findPda([Buffer.from("lp_lock_vault"), lpLock.toBuffer()], program.programId),
+        keeperTokenAccount: null,
+        systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+      })
+      .rpc();
+
+    const pool = await program.account.ammPool.fetch(ammPool);
+    expect(pool.seeded).to.be.true;
+    expect(pool.solReserve.toString()).to.equal(CONTRIBUTION.muln(9).divn(10).toString());
+    expect(pool.tokenReserve.toString()).to.equal(LIQUIDITY_TOKENS.sub(transferFee(LIQUIDITY_TOKENS)).toString());
+    expect(pool.tokenReserve.toString()).to.equal((await tokenBalance(poolTokenVault.publicKey)).toString());
+
+    const config = await program.account.tokenConfig.fetch(token.tokenConfig.publicKey);
+    expect(config.tradingEnabledAt.toNumber()).to.equal(await now());
+  });
+
+  it("taxes buys at the launch tax right after trading opens", async () => {
+    const config = await program.account.tokenConfig.fetch(token.tokenConfig.publicKey);
+    const pool = await program.account.ammPool.fetch(ammPool);
+    const amountIn = new BN(LAMPORTS_PER_SOL / 10);
+    const amountOut = amountIn
+      .muln(10000 - SWAP_FEE_BPS)
+      .divn(10000)
+      .mul(pool.tokenReserve)
+      .div(pool.solReserve.add(amountIn.muln(10000 - SWAP_FEE_BPS).divn(10000)));
+    const tax = amountOut.muln(config.launchTaxBps).divn(10000);
+
+    const userBefore = await tokenBalance(userTokenAccount);
+    const rewardsBefore = await tokenBalance(token.rewardsPool.publicKey);
+    await program.methods.swap(amountIn, new BN(0), true).accounts(swapAccounts()).rpc();
+
+    expect((await tokenBalance(userTokenAccount)).sub(userBefore).toString()).to.equal(amountOut.sub(tax).toString());
+    expect((await tokenBalance(token.rewardsPool.publicKey)).sub(rewardsBefore).toString()).to.equal(tax.toString());
+
+    const poolAfter = await program.account.ammPool.fetch(ammPool);
+    expect(poolAfter.tokenReserve.toString()).to.equal(pool.tokenReserve.sub(amountOut).toString());
+    expect(poolAfter.tokenReserve.toString()).to.equal((await tokenBalance(poolTokenVault.publicKey)).toString());
+  });
+
+  it("taxes buys and sells at the regular rates once the launch tax has decayed", async () => {
+    const config = await program.account.tokenConfig.fetch(token.tokenConfig.publicKey);
+    await warpTo(config.tradingEnabledAt.add(config.launchTaxDecaySeconds).toNumber());
+
+    // Buy
+    let pool = await program.account.ammPool.fetch(ammPool);
+    const amountIn = new BN(LAMPORTS_PER_SOL / 10);
+    const amountOut = amountIn
+      .muln(10000 - SWAP_FEE_BPS)
+      .divn(10000)
+      .mul(pool.tokenReserve)
+      .div(pool.solReserve.add(amountIn.muln(10000 - SWAP_FEE_BPS).divn(10000)));
+    const buyTax = amountOut.muln(config.buyTaxBps).divn(10000);
+    let userBefore = await tokenBalance(userTokenAccount);
+    await program.methods.swap(amountIn, amountOut.sub(buyTax), true).accounts(swapAccounts()).rpc();
+    expect((await tokenBalance(userTokenAccount)).sub(userBefore).toString()).to.equal(amountOut.sub(buyTax).toString());
+
+    // Sell
+    pool = await program.account.ammPool.fetch(ammPool);
+    const tokensIn = new BN(1_000_000).mul(UNIT);
+    const sellTax = tokensIn.muln(config.sellTaxBps).divn(10000);
+    const swapped = tokensIn.sub(sellTax);
+    const solOut = swapped
+      .muln(10000 - SWAP_FEE_BPS)
+      .divn(10000)
+      .mul(pool.solReserve)
+      .div(pool.tokenReserve.add(swapped.muln(10000 - SWAP_FEE_BPS).divn(10000)));
+    userBefore = await tokenBalance(userTokenAccount);
+    const rewardsBefore = await tokenBalance(token.rewardsPool.publicKey);
+    await program.methods.swap(tokensIn, solOut, false).accounts(swapAccounts()).rpc();
+
+    expect(userBefore.sub(await tokenBalance(userTokenAccount)).toString()).to.equal(tokensIn.toString());
+    expect((await tokenBalance(token.rewardsPool.publicKey)).sub(rewardsBefore).toString()).to.equal(sellTax.toString());
+    const poolAfter = await program.account.ammPool.fetch(ammPool);
+    expect(poolAfter.tokenReserve.toString()).to.equal(pool.tokenReserve.add(swapped).toString());
+    expect(poolAfter.solReserve.toString()).to.equal(pool.solReserve.sub(solOut).toString());
+  });
+
+  it("enforces the anti-whale limits on swaps", async () => {
+    await program.methods
+      .updateTransferLimits(new BN(1_000).mul(UNIT), new BN(0))
+      .accounts({ authority, tokenConfig: token.tokenConfig.publicKey })
+      .rpc();
+    await expectError(
+      program.methods.swap(new BN(LAMPORTS_PER_SOL), new BN(0), true).accounts(swapAccounts()).rpc(),
+      "MaxTxAmountExceeded"
+    );
+    await program.methods
+      .updateTransferLimits(new BN(0), new BN(0))
+      .accounts({ authority, tokenConfig: token.tokenConfig.publicKey })
+      .rpc();
+  });
+
+  it("adds liquidity in the pool ratio", async () => {
+    await send(
+      createAssociatedTokenAccountIdempotentInstruction(authority, userLpAccount, authority, lpMint.publicKey, TOKEN_2022_PROGRAM_ID)
+    );
+
+    const pool = await program.account.ammPool.fetch(ammPool);
+    const solAmount = new BN(LAMPORTS_PER_SOL);
+    const tokenAmount = solAmount.mul(pool.tokenReserve).add(pool.solReserve.subn(1)).div(pool.solReserve);
+    const userBefore = await tokenBalance(userTokenAccount);
+
+    await program.methods.addLiquidity(solAmount, tokenAmount, new BN(1)).accounts(liquidityAccounts()).rpc();
+
+    expect(userBefore.sub(await tokenBalance(userTokenAccount)).toString()).to.equal(tokenAmount.toString());
+    expect((await tokenBalance(userLpAccount)).gtn(0)).to.be.true;
+    const poolAfter = await program.account.ammPool.fetch(ammPool);
+    expect(poolAfter.solReserve.toString()).to.equal(pool.solReserve.add(solAmount).toString());
+    expect(poolAfter.tokenReserve.toString()).to.equal(pool.tokenReserve.add(tokenAmount.sub(transferFee(tokenAmount))).toString());
+  });
+
+  it("removes liquidity without taking more than the LP share", async () => {
+    const pool = await program.account.ammPool.fetch(ammPool);
+    const lpAmount = await tokenBalance(userLpAccount);
+    const solBefore = await lamports(poolSolVault);
+    const userBefore = await tokenBalance(userTokenAccount);
+
+    await program.methods.removeLiquidity(lpAmount, new BN(0), new BN(0)).accounts(liquidityAccounts()).rpc();
+
+    const poolAfter = await program.account.ammPool.fetch(ammPool);
+    const solOut = solBefore.sub(await lamports(poolSolVault));
+    const tokenOut = (await tokenBalance(userTokenAccount)).sub(userBefore);
+    expect((await tokenBalance(userLpAccount)).isZero()).to.be.true;
+    expect(poolAfter.solReserve.toString()).to.equal(pool.solReserve.sub(solOut).toString());
+    // The withdrawal pays the mint's transfer fee on the way out
+    const tokenWithdrawn = pool.tokenReserve.sub(poolAfter.tokenReserve);
+    expect(tokenOut.toString()).to.equal(tokenWithdrawn.sub(transferFee(tokenWithdrawn)).toString());
+    expect(poolAfter.tokenReserve.toString()).to.equal((await tokenBalance(poolTokenVault.publicKey)).toString());
+    // Only the liquidity added above comes back out, rounded down
+    expect(solOut.lte(new BN(LAMPORTS_PER_SOL))).to.be.true;
+  });
+});
@@ -0,0 +1,207 @@
+// Stand-in for the Metaplex Token Metadata program, loaded at the Metaplex address in local tests.
+// Only implements the two instructions the CHAD program calls, with the same argument layouts.
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint,
+    entrypoint::ProgramResult,
+    program::invoke_signed,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::Sysvar,
+};
+
+// Metaplex instruction indexes
+const CREATE_METADATA_ACCOUNT_V3: u8 = 33;
+const UPDATE_METADATA_ACCOUNT_V2: u8 = 15;
+
+// Metaplex limits on the metadata fields
+const MAX_NAME_LENGTH: usize = 32;
+const MAX_SYMBOL_LENGTH: usize = 10;
+const MAX_URI_LENGTH: usize = 200;
+
+// Error returned when updating an immutable account
+pub const DATA_IS_IMMUTABLE: u32 = 0;
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct Creator {
+    pub address: Pubkey,
+    pub verified: bool,
+    pub share: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct Collection {
+    pub verified: bool,
+    pub key: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct Uses {
+    pub use_method: u8,
+    pub remaining: u64,
+    pub total: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub enum CollectionDetails {
+    V1 { size: u64 },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Clone)]
+pub struct DataV2 {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub seller_fee_basis_points: u16,
+    pub creators: Option<Vec<Creator>>,
+    pub collection: Option<Collection>,
+    pub uses: Option<Uses>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct CreateMetadataAccountArgsV3 {
+    data: DataV2,
+    is_mutable: bool,
+    collection_details: Option<CollectionDetails>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize)]
+struct UpdateMetadataAccountArgsV2 {
+    data: Option<DataV2>,
+    update_authority: Option<Pubkey>,
+    primary_sale_happened: Option<bool>,
+    is_mutable: Option<bool>,
+}
+
+// Simplified metadata account, read back by the tests
+#[derive(BorshSerialize, BorshDeserialize)]
+pub struct Metadata {
+    pub update_authority: Pubkey,
+    pub mint: Pubkey,
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+    pub is_mutable: bool,
+}
+
+impl Metadata {
+    pub const LEN: usize = 32 + 32 + (4 + MAX_NAME_LENGTH) + (4 + MAX_SYMBOL_LENGTH) + (4 + MAX_URI_LENGTH) + 1;
+}
+
+#[cfg(not(feature = "no-entrypoint"))]
+entrypoint!(process_instruction);
+
+pub fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (index, args) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+
+    match *index {
+        CREATE_METADATA_ACCOUNT_V3 => create_metadata_account(program_id, accounts, args),
+        UPDATE_METADATA_ACCOUNT_V2 => update_metadata_account(program_id, accounts, args),
+        _ => Err(ProgramError::InvalidInstructionData),
+    }
+}
+
+fn create_metadata_account(program_id: &Pubkey, accounts: &[AccountInfo], args: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let metadata = next_account_info(accounts_iter)?;
+    let mint = next_account_info(accounts_iter)?;
+    let mint_authority = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let update_authority = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    if !mint_authority.is_signer || !payer.is_signer {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    let args = CreateMetadataAccountArgsV3::try_from_slice(args)?;
+    check_data(&args.data)?;
+
+    let (expected, bump) = Pubkey::find_program_address(
+        &[b"metadata", program_id.as_ref(), mint.key.as_ref()],
+        program_id,
+    );
+    if expected != *metadata.key {
+        return Err(ProgramError::InvalidSeeds);
+    }
+
+    invoke_signed(
+        &system_instruction::create_account(
+            payer.key,
+            metadata.key,
+            Rent::get()?.minimum_balance(Metadata::LEN),
+            Metadata::LEN as u64,
+            program_id,
+        ),
+        &[payer.clone(), metadata.clone(), system_program.clone()],
+        &[&[b"metadata", program_id.as_ref(), mint.key.as_ref(), &[bump]]],
+    )?;
+
+    let account = Metadata {
+        update_authority: *update_authority.key,
+        mint: *mint.key,
+        name: args.data.name,
+        symbol: args.data.symbol,
+        uri: args.data.uri,
+        is_mutable: args.is_mutable,
+    };
+    account.serialize(&mut &mut metadata.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+fn update_metadata_account(program_id: &Pubkey, accounts: &[AccountInfo], args: &[u8]) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let metadata = next_account_info(accounts_iter)?;
+    let update_authority = next_account_info(accounts_iter)?;
+
+    if metadata.owner != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let mut account = Metadata::deserialize(&mut &metadata.data.borrow()[..])?;
+    if !update_authority.is_signer || account.update_authority != *update_authority.key {
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+    // Same as Metaplex: an immutable account can't be updated anymore
+    if !account.is_mutable {
+        return Err(ProgramError::Custom(DATA_IS_IMMUTABLE));
+    }
+
+    let args = UpdateMetadataAccountArgsV2::try_from_slice(args)?;
+    if let Some(data) = args.data {
+        check_data(&data)?;
+        account.name = data.name;
+        account.symbol = data.symbol;
+        account.uri = data.uri;
+    }
+    if let Some(update_authority) = args.update_authority {
+        account.update_authority = update_authority;
+    }
+    if let Some(is_mutable) = args.is_mutable {
+        account.is_mutable = is_mutable;
+    }
+    account.serialize(&mut &mut metadata.data.borrow_mut()[..])?;
+
+    Ok(())
+}
+
+// Helper function to apply the Metaplex limits on the metadata fields
+fn check_data(data: &DataV2) -> ProgramResult {
+    if data.name.len() > MAX_NAME_LENGTH
+        || data.symbol.len() > MAX_SYMBOL_LENGTH
+        || data.uri.len() > MAX_URI_LENGTH
+    {
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
@@ -0,0 +1,123 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { ChadEmpire } from "../target/types/chad_empire";
+import { ChadToken, initializeToken, METADATA_PROGRAM_ID } from "./helpers";
+
+// Decodes the metadata account written by the stand-in metadata program
+function decodeMetadata(data: Buffer) {
+  let offset = 0;
+  const readKey = () => {
+    const key = new PublicKey(data.subarray(offset, offset + 32));
+    offset += 32;
+    return key;
+  };
+  const readString = () => {
+    const length = data.readUInt32LE(offset);
+    const value = data.subarray(offset + 4, offset + 4 + length).toString("utf8");
+    offset += 4 + length;
+    return value;
+  };
+
+  const updateAuthority = readKey();
+  const mint = readKey();
+  const name = readString();
+  const symbol = readString();
+  const uri = readString();
+  const isMutable = data[offset] === 1;
+  return { updateAuthority, mint, name, symbol, uri, isMutable };
+}
+
+describe("token metadata", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+  const program = anchor.workspace.ChadEmpire as Program<ChadEmpire>;
+
+  let token: ChadToken;
+
+  const fetchMetadata = async () => {
+    const account = await provider.connection.getAccountInfo(token.metadata);
+    expect(account.owner.toBase58()).to.equal(METADATA_PROGRAM_ID.toBase58());
+    return decodeMetadata(account.data);
+  };
+
+  const updateAccounts = (authority: PublicKey) => ({
+    authority,
+    tokenConfig: token.tokenConfig.publicKey,
+    metadata: token.metadata,
+    metadataAuthority: token.metadataAuthority,
+    metadataProgram: METADATA_PROGRAM_ID,
+  });
+
+  before(async () => {
+    token = await initializeToken(program);
+  });
+
+  it("creates the metadata account at bootstrap", async () => {
+    const metadata = await fetchMetadata();
+    expect(metadata.mint.toBase58()).to.equal(token.mint.publicKey.toBase58());
+    expect(metadata.updateAuthority.toBase58()).to.equal(token.metadataAuthority.toBase58());
+    expect(metadata.name).to.equal("ChadEmpire");
+    expect(metadata.symbol).to.equal("CHAD");
+    expect(metadata.isMutable).to.be.true;
+  });
+
+  it("updates the metadata", async () => {
+    await program.methods
+      .updateTokenMetadata("Chad Empire", "CHAD", "https://chadempire.io/v2.json")
+      .accounts(updateAccounts(provider.wallet.publicKey))
+      .rpc();
+
+    const metadata = await fetchMetadata();
+    expect(metadata.name).to.equal("Chad Empire");
+    expect(metadata.uri).to.equal("https://chadempire.io/v2.json");
+  });
+
+  it("rejects updates from other signers", async () => {
+    const other = anchor.web3.Keypair.generate();
+    try {
+      await program.methods
+        .updateTokenMetadata("Not Chad", "NOPE", "https://example.com")
+        .accounts(updateAccounts(other.publicKey))
+        .signers([other])
+        .rpc();
+      expect.fail("update by a non-authority should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("Unauthorized");
+    }
+  });
+
+  it("rejects fields over the Metaplex limits", async () => {
+    try {
+      await program.methods
+        .updateTokenMetadata("Chad Empire", "CHADCHADCHAD", "https://chadempire.io/v2.json")
+        .accounts(updateAccounts(provider.wallet.publicKey))
+        .rpc();
+      expect.fail("symbol over 10 bytes should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MetadataTooLong");
+    }
+  });
+
+  it("locks the metadata", async () => {
+    await program.methods
+      .lockTokenMetadata()
+      .accounts(updateAccounts(provider.wallet.publicKey))
+      .rpc();
+
+    const config = await program.account.tokenConfig.fetch(token.tokenConfig.publicKey);
+    expect(config.metadataLocked).to.be.true;
+    expect((await fetchMetadata()).isMutable).to.be.false;
+
+    try {
+      await program.methods
+        .updateTokenMetadata("Chad Empire", "CHAD", "https://chadempire.io/v3.json")
+        .accounts(updateAccounts(provider.wallet.publicKey))
+        .rpc();
+      expect.fail("update after lock should fail");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MetadataLocked");
+    }
+  });
+});